@@ -0,0 +1,87 @@
+//! Compares request throughput when a synchronous, diesel-style query runs directly on the
+//! async worker threads versus on tokio's blocking pool, as `data::blocking::with_connection`
+//! does. The query is simulated with a fixed sleep so the numbers do not depend on a database.
+//!
+//! Run with `cargo bench -p blog_server`.
+
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+use r2d2::{ManageConnection, Pool};
+use tokio::runtime::Builder;
+
+const WORKER_THREADS: usize = 4;
+const POOL_SIZE: u32 = 16;
+const REQUESTS: usize = 256;
+const QUERY_LATENCY: Duration = Duration::from_millis(5);
+
+struct FakeConnection;
+
+impl FakeConnection {
+    fn query(&mut self) -> u64 {
+        std::thread::sleep(QUERY_LATENCY);
+        1
+    }
+}
+
+struct FakeManager;
+
+impl ManageConnection for FakeManager {
+    type Connection = FakeConnection;
+    type Error = Infallible;
+
+    fn connect(&self) -> Result<FakeConnection, Infallible> {
+        Ok(FakeConnection)
+    }
+
+    fn is_valid(&self, _: &mut FakeConnection) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn has_broken(&self, _: &mut FakeConnection) -> bool {
+        false
+    }
+}
+
+async fn blocking_in_place(pool: Pool<FakeManager>) -> u64 {
+    pool.get().unwrap().query()
+}
+
+async fn on_blocking_pool(pool: Pool<FakeManager>) -> u64 {
+    tokio::task::spawn_blocking(move || pool.get().unwrap().query()).await.unwrap()
+}
+
+fn run<F, Fut>(name: &str, handler: F)
+where
+    F: Fn(Pool<FakeManager>) -> Fut,
+    Fut: std::future::Future<Output = u64> + Send + 'static,
+{
+    let runtime = Builder::new_multi_thread().worker_threads(WORKER_THREADS).enable_all().build().unwrap();
+    let pool = Pool::builder().max_size(POOL_SIZE).build(FakeManager).unwrap();
+
+    let elapsed = runtime.block_on(async {
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..REQUESTS).map(|_| tokio::spawn(handler(pool.clone()))).collect();
+        let mut total = 0;
+        for task in tasks {
+            total += task.await.unwrap();
+        }
+        assert_eq!(total, REQUESTS as u64);
+        start.elapsed()
+    });
+
+    println!(
+        "{name:<20} {REQUESTS} requests in {:>8.1?} ({:>7.1} req/s)",
+        elapsed,
+        REQUESTS as f64 / elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    println!(
+        "{WORKER_THREADS} worker threads, pool of {POOL_SIZE} connections, {}ms per query",
+        QUERY_LATENCY.as_millis()
+    );
+    run("blocking in place", blocking_in_place);
+    run("spawn_blocking", on_blocking_pool);
+}
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop table sessions;
+
+ALTER TABLE "users" DROP COLUMN "password_hash";
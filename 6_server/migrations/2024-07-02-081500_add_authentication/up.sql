@@ -0,0 +1,18 @@
+-- Your SQL goes here
+ALTER TABLE "users"
+    ADD COLUMN "password_hash" TEXT;
+
+create table sessions
+(
+    token_hash text                                  not null
+        constraint sessions_pk
+            primary key,
+    user_id    uuid                                  not null
+        constraint sessions_user_id_fkey
+            references users
+            on delete cascade,
+    created_at timestamp with time zone default now() not null,
+    expires_at timestamp with time zone               not null
+);
+
+create index sessions_user_id_idx on sessions (user_id);
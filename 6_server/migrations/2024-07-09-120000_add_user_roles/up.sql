@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE "users"
+    ADD COLUMN "role" TEXT NOT NULL DEFAULT 'reader'
+        CONSTRAINT "users_role_check" CHECK ("role" IN ('reader', 'author', 'editor', 'admin'));
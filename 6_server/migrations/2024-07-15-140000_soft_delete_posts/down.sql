@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop index posts_deleted_at_idx;
+
+ALTER TABLE "posts" DROP COLUMN "deleted_at";
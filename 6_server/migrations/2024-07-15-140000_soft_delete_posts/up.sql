@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "posts"
+    ADD COLUMN "deleted_at" TIMESTAMP WITH TIME ZONE;
+
+create index posts_deleted_at_idx on posts (deleted_at) where deleted_at is not null;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop index posts_title_id_idx;
+drop index posts_created_at_id_idx;
+
+ALTER TABLE "posts" DROP COLUMN "created_at";
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE "posts"
+    ADD COLUMN "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
+
+create index posts_created_at_id_idx on posts (created_at, id);
+create index posts_title_id_idx on posts (title, id);
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS set_updated_at ON "users";
+
+ALTER TABLE "users"
+    DROP COLUMN "created_at",
+    DROP COLUMN "updated_at";
+
+DROP TRIGGER IF EXISTS set_updated_at ON "posts";
+
+ALTER TABLE "posts"
+    DROP COLUMN "updated_at",
+    DROP COLUMN "published_at";
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE "posts"
+    ADD COLUMN "updated_at"   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    ADD COLUMN "published_at" TIMESTAMP WITH TIME ZONE;
+
+UPDATE "posts" SET "updated_at" = "created_at", "published_at" = CASE WHEN "published" THEN "created_at" END;
+
+SELECT diesel_manage_updated_at('posts');
+
+ALTER TABLE "users"
+    ADD COLUMN "created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    ADD COLUMN "updated_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
+
+SELECT diesel_manage_updated_at('users');
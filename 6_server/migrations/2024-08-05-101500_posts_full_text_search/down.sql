@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+drop index posts_search_vector_idx;
+
+ALTER TABLE "posts" DROP COLUMN "search_vector";
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE "posts"
+    ADD COLUMN "search_vector" TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', "title"), 'A') ||
+        setweight(to_tsvector('english', "body"), 'B')
+        ) STORED;
+
+create index posts_search_vector_idx on posts using gin (search_vector);
@@ -0,0 +1,29 @@
+-- Your SQL goes here
+create table comments
+(
+    id         uuid                     default gen_random_uuid() not null
+        constraint comments_pk
+            primary key,
+    post_id    uuid                                               not null
+        constraint comments_post_id_fkey
+            references posts
+            on delete cascade,
+    author_id  uuid                                               not null
+        constraint comments_author_id_fkey
+            references users
+            on delete cascade,
+    parent_id  uuid
+        constraint comments_parent_id_fkey
+            references comments
+            on delete cascade,
+    body       text                                               not null,
+    status     text                     default 'pending'         not null
+        constraint comments_status_check
+            check (status in ('pending', 'approved', 'rejected')),
+    created_at timestamp with time zone default now()             not null,
+    updated_at timestamp with time zone default now()             not null
+);
+
+create index comments_post_id_idx on comments (post_id, created_at);
+
+SELECT diesel_manage_updated_at('comments');
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+create table tags
+(
+    id   uuid default gen_random_uuid() not null
+        constraint tags_pk
+            primary key,
+    name text                           not null
+        constraint tags_name_key
+            unique
+);
+
+create table post_tags
+(
+    post_id uuid not null
+        constraint post_tags_post_id_fkey
+            references posts
+            on delete cascade,
+    tag_id  uuid not null
+        constraint post_tags_tag_id_fkey
+            references tags
+            on delete cascade,
+    constraint post_tags_pk
+        primary key (post_id, tag_id)
+);
+
+create index post_tags_tag_id_idx on post_tags (tag_id);
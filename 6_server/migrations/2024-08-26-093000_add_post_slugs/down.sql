@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop table post_slugs;
+
+alter table posts
+    drop column slug;
@@ -0,0 +1,47 @@
+-- Your SQL goes here
+alter table posts
+    add slug text;
+
+-- Oldest post first, each takes the first of `base`, `base-2`, `base-3`, ... no post has yet.
+-- Checking against the slugs already assigned keeps e.g. "x", "x" and "x 2" from colliding.
+do
+$$
+    declare
+        post      record;
+        base      text;
+        candidate text;
+        n         integer;
+    begin
+        for post in select id, title from posts order by created_at, id
+            loop
+                base := coalesce(nullif(trim(both '-' from regexp_replace(lower(post.title), '[^[:alnum:]]+', '-', 'g')), ''),
+                                 'post');
+                candidate := base;
+                n := 1;
+                while exists(select 1 from posts where slug = candidate)
+                    loop
+                        n := n + 1;
+                        candidate := base || '-' || n;
+                    end loop;
+                update posts set slug = candidate where id = post.id;
+            end loop;
+    end
+$$;
+
+alter table posts
+    alter column slug set not null,
+    add constraint posts_slug_key unique (slug);
+
+create table post_slugs
+(
+    slug       text                                  not null
+        constraint post_slugs_pk
+            primary key,
+    post_id    uuid                                  not null
+        constraint post_slugs_post_id_fkey
+            references posts
+            on delete cascade,
+    created_at timestamp with time zone default now() not null
+);
+
+create index post_slugs_post_id_idx on post_slugs (post_id);
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+alter table posts
+    add published boolean default false not null;
+
+update posts
+set published = true
+where status = 'published';
+
+alter table posts
+    drop column publish_at,
+    drop column status;
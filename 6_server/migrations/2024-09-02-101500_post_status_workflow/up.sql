@@ -0,0 +1,15 @@
+-- Your SQL goes here
+alter table posts
+    add status     text default 'draft' not null
+        constraint posts_status_check
+            check (status in ('draft', 'in_review', 'scheduled', 'published', 'archived')),
+    add publish_at timestamp with time zone;
+
+update posts
+set status = 'published'
+where published;
+
+alter table posts
+    drop column published;
+
+create index posts_scheduled_publish_at_idx on posts (publish_at) where status = 'scheduled';
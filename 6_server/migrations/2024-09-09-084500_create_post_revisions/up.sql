@@ -0,0 +1,22 @@
+-- Your SQL goes here
+create table post_revisions
+(
+    post_id    uuid                                   not null
+        constraint post_revisions_post_id_fkey
+            references posts
+            on delete cascade,
+    revision   integer                                not null,
+    title      text                                   not null,
+    body       text                                   not null,
+    editor_id  uuid
+        constraint post_revisions_editor_id_fkey
+            references users
+            on delete set null,
+    created_at timestamp with time zone default now() not null,
+    constraint post_revisions_pk
+        primary key (post_id, revision)
+);
+
+insert into post_revisions (post_id, revision, title, body, editor_id, created_at)
+select id, 1, title, body, author_id, updated_at
+from posts;
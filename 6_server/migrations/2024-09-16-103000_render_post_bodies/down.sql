@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+alter table posts
+    drop column reading_time_minutes,
+    drop column excerpt,
+    drop column body_html;
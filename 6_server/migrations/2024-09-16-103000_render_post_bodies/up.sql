@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Left null for existing posts, which are rendered right before 2024-09-30-090000_require_rendered_post_bodies.
+alter table posts
+    add body_html            text,
+    add excerpt              text,
+    add reading_time_minutes integer;
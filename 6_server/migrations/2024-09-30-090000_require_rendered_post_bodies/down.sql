@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+alter table posts
+    alter column body_html drop not null,
+    alter column excerpt drop not null,
+    alter column reading_time_minutes drop not null;
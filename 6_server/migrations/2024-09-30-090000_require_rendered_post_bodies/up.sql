@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Existing posts are rendered by the backfill `Postgres::migrate` runs right before this.
+alter table posts
+    alter column body_html set not null,
+    alter column excerpt set not null,
+    alter column reading_time_minutes set not null;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+drop table post_revisions;
+drop table post_tags;
+drop table tags;
+drop table comments;
+drop table post_slugs;
+drop table posts;
+drop table sessions;
+drop table users;
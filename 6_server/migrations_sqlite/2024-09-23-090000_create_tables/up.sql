@@ -0,0 +1,154 @@
+-- Your SQL goes here
+-- Ids are stored as hyphenated UUID text and timestamps as RFC 3339 like text in UTC,
+-- both generated by the application.
+create table users
+(
+    id            text not null
+        constraint users_pk
+            primary key,
+    username      text not null
+        constraint users_name_key
+            unique,
+    email         text not null
+        constraint users_email_key
+            unique,
+    password_hash text,
+    role          text default 'reader' not null
+        constraint users_role_check
+            check (role in ('reader', 'author', 'editor', 'admin')),
+    created_at    text not null,
+    updated_at    text not null
+);
+
+create table sessions
+(
+    token_hash text not null
+        constraint sessions_pk
+            primary key,
+    user_id    text not null
+        constraint sessions_user_id_fkey
+            references users
+            on delete cascade,
+    created_at text not null,
+    expires_at text not null
+);
+
+create index sessions_user_id_idx on sessions (user_id);
+
+create table posts
+(
+    id                   text not null
+        constraint posts_pk
+            primary key,
+    title                text not null,
+    slug                 text not null
+        constraint posts_slug_key
+            unique,
+    body                 text not null,
+    body_html            text    not null,
+    excerpt              text    not null,
+    reading_time_minutes integer not null,
+    status               text default 'draft' not null
+        constraint posts_status_check
+            check (status in ('draft', 'in_review', 'scheduled', 'published', 'archived')),
+    author_id            text not null
+        constraint posts_author_id_fkey
+            references users
+            on delete cascade,
+    deleted_at           text,
+    created_at           text not null,
+    updated_at           text not null,
+    publish_at           text,
+    published_at         text
+);
+
+create index posts_author_id_idx on posts (author_id);
+create index posts_deleted_at_idx on posts (deleted_at) where deleted_at is not null;
+create index posts_created_at_id_idx on posts (created_at, id);
+create index posts_title_id_idx on posts (title, id);
+create index posts_scheduled_publish_at_idx on posts (publish_at) where status = 'scheduled';
+
+create table post_slugs
+(
+    slug       text not null
+        constraint post_slugs_pk
+            primary key,
+    post_id    text not null
+        constraint post_slugs_post_id_fkey
+            references posts
+            on delete cascade,
+    created_at text not null
+);
+
+create index post_slugs_post_id_idx on post_slugs (post_id);
+
+create table comments
+(
+    id         text not null
+        constraint comments_pk
+            primary key,
+    post_id    text not null
+        constraint comments_post_id_fkey
+            references posts
+            on delete cascade,
+    author_id  text not null
+        constraint comments_author_id_fkey
+            references users
+            on delete cascade,
+    parent_id  text
+        constraint comments_parent_id_fkey
+            references comments
+            on delete cascade,
+    body       text not null,
+    status     text default 'pending' not null
+        constraint comments_status_check
+            check (status in ('pending', 'approved', 'rejected')),
+    created_at text not null,
+    updated_at text not null
+);
+
+create index comments_post_id_idx on comments (post_id, created_at);
+
+create table tags
+(
+    id   text not null
+        constraint tags_pk
+            primary key,
+    name text not null
+        constraint tags_name_key
+            unique
+);
+
+create table post_tags
+(
+    post_id text not null
+        constraint post_tags_post_id_fkey
+            references posts
+            on delete cascade,
+    tag_id  text not null
+        constraint post_tags_tag_id_fkey
+            references tags
+            on delete cascade,
+    constraint post_tags_pk
+        primary key (post_id, tag_id)
+);
+
+create index post_tags_tag_id_idx on post_tags (tag_id);
+
+create table post_revisions
+(
+    post_id    text    not null
+        constraint post_revisions_post_id_fkey
+            references posts
+            on delete cascade,
+    revision   integer not null,
+    title      text    not null,
+    body       text    not null,
+    editor_id  text
+        constraint post_revisions_editor_id_fkey
+            references users
+            on delete set null,
+    created_at text    not null,
+    constraint post_revisions_pk
+        primary key (post_id, revision)
+);
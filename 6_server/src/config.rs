@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
 
 use envconfig::Envconfig;
 
@@ -8,18 +10,180 @@ pub struct Config {
     host: IpAddr,
     #[envconfig(from = "PORT", default = "8080")]
     port: u16,
+    #[envconfig(from = "STORAGE", default = "database")]
+    pub storage: Storage,
+    #[envconfig(from = "LOG_FORMAT", default = "pretty")]
+    pub log_format: LogFormat,
     #[envconfig(nested = true)]
     pub db: DbConfig,
+    #[envconfig(nested = true)]
+    pub jobs: JobsConfig,
+    #[envconfig(nested = true)]
+    pub site: SiteConfig,
+    #[envconfig(nested = true)]
+    pub telemetry: TelemetryConfig,
+}
+
+/// Where the server keeps its data. `Memory` needs no database and loses everything on exit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Storage {
+    Database,
+    Memory,
+}
+
+impl FromStr for Storage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "database" => Ok(Storage::Database),
+            "memory" => Ok(Storage::Memory),
+            _ => Err(anyhow::anyhow!("unknown storage: {}", s)),
+        }
+    }
+}
+
+/// `Pretty` is the human readable format for local development, `Json` emits one object per
+/// line with the fields of the enclosing spans for log aggregators.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(anyhow::anyhow!("unknown log format: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DbBackend {
+    Postgres,
+    Sqlite,
 }
 
 #[derive(Envconfig)]
 pub struct DbConfig {
+    /// Only required with `Storage::Database`.
     #[envconfig(from = "DATABASE_URL")]
+    pub url: Option<String>,
+    /// Applies pending embedded migrations at startup instead of only checking the schema.
+    #[envconfig(from = "RUN_MIGRATIONS", default = "false")]
+    pub run_migrations: bool,
+}
+
+impl DbConfig {
+    pub fn url(&self) -> Result<&str, anyhow::Error> {
+        self.url.as_deref().ok_or_else(|| anyhow::anyhow!("DATABASE_URL is not set"))
+    }
+
+    /// Picks the backend from the URL scheme, e.g. `postgres://...` or `sqlite://blog.db`.
+    pub fn backend(&self) -> Result<DbBackend, anyhow::Error> {
+        match self.url()?.split_once(':').map(|(scheme, _)| scheme) {
+            Some("postgres" | "postgresql") => Ok(DbBackend::Postgres),
+            Some("sqlite") => Ok(DbBackend::Sqlite),
+            scheme => Err(anyhow::anyhow!("unsupported DATABASE_URL scheme: {}", scheme.unwrap_or_default())),
+        }
+    }
+}
+
+#[derive(Envconfig)]
+pub struct JobsConfig {
+    #[envconfig(from = "POST_RETENTION_DAYS", default = "30")]
+    pub post_retention_days: i64,
+    #[envconfig(from = "PURGE_INTERVAL_SECS", default = "3600")]
+    pub purge_interval_secs: u64,
+    #[envconfig(from = "PUBLISH_INTERVAL_SECS", default = "60")]
+    pub publish_interval_secs: u64,
+}
+
+/// Public facing details of the blog, used wherever absolute links are needed.
+#[derive(Envconfig, Clone, Debug)]
+pub struct SiteConfig {
+    #[envconfig(from = "SITE_URL", default = "http://localhost:8080")]
     pub url: String,
+    #[envconfig(from = "SITE_TITLE", default = "Blog")]
+    pub title: String,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        SiteConfig::init_from_hashmap(&HashMap::new()).expect("every site setting has a default")
+    }
+}
+
+/// The standard OpenTelemetry variables, traces are exported once an endpoint is set.
+#[derive(Envconfig)]
+pub struct TelemetryConfig {
+    #[envconfig(from = "OTEL_SDK_DISABLED", default = "false")]
+    pub disabled: bool,
+    #[envconfig(from = "OTEL_SERVICE_NAME", default = "blog_server")]
+    pub service_name: String,
+    /// Base URL of the collector, `/v1/traces` is appended.
+    #[envconfig(from = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    pub endpoint: Option<String>,
+    /// Full URL for traces only, takes precedence over `endpoint`.
+    #[envconfig(from = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")]
+    pub traces_endpoint: Option<String>,
+    #[envconfig(from = "OTEL_EXPORTER_OTLP_PROTOCOL", default = "http/protobuf")]
+    pub protocol: String,
+}
+
+impl TelemetryConfig {
+    pub fn traces_endpoint(&self) -> Option<String> {
+        if self.disabled {
+            return None;
+        }
+        self.traces_endpoint.clone()
+            .or_else(|| self.endpoint.as_ref().map(|endpoint| format!("{}/v1/traces", endpoint.trim_end_matches('/'))))
+    }
 }
 
 impl Config {
     pub fn to_socket_addr(&self) -> SocketAddr {
         SocketAddr::new(self.host, self.port)
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn db_config(url: &str) -> DbConfig {
+        DbConfig { url: Some(url.to_string()), run_migrations: false }
+    }
+
+    #[test]
+    fn test_db_backend_from_url_scheme() {
+        assert_eq!(db_config("postgres://postgres@localhost/blog").backend().unwrap(), DbBackend::Postgres);
+        assert_eq!(db_config("postgresql://localhost/blog").backend().unwrap(), DbBackend::Postgres);
+        assert_eq!(db_config("sqlite://blog.db").backend().unwrap(), DbBackend::Sqlite);
+        assert!(db_config("mysql://localhost/blog").backend().is_err());
+        assert!(DbConfig { url: None, run_migrations: false }.backend().is_err());
+    }
+
+    #[test]
+    fn test_traces_endpoint() {
+        let telemetry = |vars: &[(&str, &str)]| {
+            let vars = vars.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+            TelemetryConfig::init_from_hashmap(&vars).unwrap()
+        };
+
+        assert_eq!(telemetry(&[]).traces_endpoint(), None);
+        assert_eq!(telemetry(&[("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318/")]).traces_endpoint(),
+                   Some("http://collector:4318/v1/traces".to_string()));
+        assert_eq!(telemetry(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318"),
+            ("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT", "http://traces:4318/custom"),
+        ]).traces_endpoint(), Some("http://traces:4318/custom".to_string()));
+        assert_eq!(telemetry(&[("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318"), ("OTEL_SDK_DISABLED", "true")]).traces_endpoint(), None);
+    }
+}
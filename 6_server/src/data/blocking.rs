@@ -0,0 +1,39 @@
+use r2d2::{ManageConnection, Pool};
+use tracing::{dispatcher, info_span, Span};
+
+use crate::data::data_errors::DataError;
+use crate::models::health::PoolStatus;
+
+/// Runs `f` with a pooled connection on tokio's blocking thread pool, so synchronous
+/// database calls never stall the async worker threads. The query runs in the caller's span,
+/// waiting for the blocking thread and a free connection shows up as `db.pool.checkout`.
+pub async fn with_connection<M, T, F>(pool: &Pool<M>, f: F) -> Result<T, DataError>
+where
+    M: ManageConnection,
+    T: Send + 'static,
+    F: FnOnce(&mut M::Connection) -> Result<T, DataError> + Send + 'static,
+    DataError: From<r2d2::Error>,
+{
+    let pool = pool.clone();
+    // The blocking thread does not inherit a thread local subscriber, e.g. one set in tests.
+    let dispatch = dispatcher::get_default(Clone::clone);
+    let span = Span::current();
+    let checkout = info_span!("db.pool.checkout");
+
+    tokio::task::spawn_blocking(move || dispatcher::with_default(&dispatch, || {
+        let mut conn = checkout.in_scope(|| pool.get())?;
+        drop(checkout);
+        span.in_scope(|| f(&mut conn))
+    }))
+        .await
+        .map_err(|e| DataError::InternalServerError(e.into()))?
+}
+
+pub fn pool_status<M: ManageConnection>(pool: &Pool<M>) -> PoolStatus {
+    let state = pool.state();
+    PoolStatus {
+        max_size: pool.max_size(),
+        connections: state.connections,
+        idle: state.idle_connections,
+    }
+}
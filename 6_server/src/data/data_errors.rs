@@ -1,5 +1,6 @@
 use thiserror::Error;
-use tracing::error;
+
+use crate::models::post::PostStatus;
 
 #[derive(Debug, Error)]
 pub enum DataError {
@@ -7,6 +8,16 @@ pub enum DataError {
     NotFound,
     #[error("entity already exists")]
     Duplicate,
+    #[error("unauthorized")]
+    Unauthorized,
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid cursor")]
+    InvalidCursor,
+    #[error("user still owns posts, including deleted ones not purged yet")]
+    UserHasPosts,
+    #[error("cannot move post from {from} to {to}")]
+    InvalidTransition { from: PostStatus, to: PostStatus },
     #[error("internal server error")]
     InternalServerError(anyhow::Error),
 }
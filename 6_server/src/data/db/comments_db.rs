@@ -0,0 +1,116 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::db::postgres::Postgres;
+use crate::data::db::schema::comments;
+use crate::data::db::schema::comments::dsl::*;
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::models::comment::{Comment, CommentBody, CreateComment, ModerationStatus};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = comments)]
+struct DbComment {
+    id: Uuid,
+    post_id: Uuid,
+    author_id: Uuid,
+    parent_id: Option<Uuid>,
+    body: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbComment> for Comment {
+    type Error = Error;
+
+    fn try_from(value: DbComment) -> Result<Self, Self::Error> {
+        Ok(Comment {
+            id: value.id,
+            post_id: value.post_id,
+            author: value.author_id,
+            parent_id: value.parent_id,
+            body: CommentBody::try_new(value.body)?,
+            status: value.status.parse()?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = comments)]
+struct DbCreateComment {
+    post_id: Uuid,
+    author_id: Uuid,
+    parent_id: Option<Uuid>,
+    body: String,
+}
+
+#[async_trait]
+impl CommentRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_comment(&self, post: Uuid, author: Uuid, create_comment: CreateComment) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            conn.transaction::<_, DataError, _>(|conn| {
+                if let Some(parent) = create_comment.parent_id {
+                    comments.find(parent).filter(post_id.eq(post))
+                        .select(id)
+                        .get_result::<Uuid>(conn)?;
+                }
+
+                Ok(diesel::insert_into(comments)
+                    .values(DbCreateComment {
+                        post_id: post,
+                        author_id: author,
+                        parent_id: create_comment.parent_id,
+                        body: create_comment.body.to_string(),
+                    })
+                    .returning(DbComment::as_returning())
+                    .get_result::<DbComment>(conn)?.try_into()?)
+            })
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_comment(&self, comment_id: Uuid) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            Ok(comments.find(comment_id)
+                .select(DbComment::as_select())
+                .get_result::<DbComment>(conn)?.try_into()?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_comments(&self, post: Uuid, with_status: Option<ModerationStatus>) -> Result<Vec<Comment>, DataError> {
+        self.run(move |conn| {
+            let mut query = comments
+                .filter(post_id.eq(post))
+                .select(DbComment::as_select())
+                .order((created_at.asc(), id.asc()))
+                .into_boxed();
+
+            if let Some(s) = with_status {
+                query = query.filter(status.eq(s.as_str()));
+            }
+
+            query.get_results(conn)?
+                .into_iter()
+                .map(DbComment::try_into).collect::<Result<Vec<Comment>, Error>>().map_err(|e| e.into())
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_comment_status(&self, comment_id: Uuid, new_status: ModerationStatus) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            Ok(diesel::update(comments.find(comment_id))
+                .set(status.eq(new_status.as_str()))
+                .returning(DbComment::as_returning())
+                .get_result::<DbComment>(conn)?.try_into()?)
+        }).await
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use diesel::RunQueryDsl;
+use tracing::instrument;
+
+use crate::data::blocking::pool_status;
+use crate::data::data_errors::DataError;
+use crate::data::db::postgres::{Postgres, MIGRATIONS};
+use crate::data::migrations::pending_migrations;
+use crate::data::repositories::health_repository::HealthRepository;
+use crate::models::health::PoolStatus;
+
+#[async_trait]
+impl HealthRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn ping(&self) -> Result<(), DataError> {
+        self.run(move |conn| {
+            diesel::sql_query("SELECT 1").execute(conn)?;
+            Ok(())
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn pending_migrations(&self) -> Result<Vec<String>, DataError> {
+        self.run(move |conn| Ok(pending_migrations(conn, MIGRATIONS)?)).await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        Some(pool_status(&self.pool))
+    }
+}
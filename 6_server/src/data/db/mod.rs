@@ -1,5 +1,11 @@
 pub mod users_db;
 pub mod posts_db;
+pub mod sessions_db;
+pub mod search_db;
+pub mod comments_db;
+pub mod tags_db;
+pub mod revisions_db;
+pub mod health_db;
 pub mod postgres;
 mod schema;
 mod db_error;
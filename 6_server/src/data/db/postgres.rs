@@ -1,9 +1,26 @@
-use diesel::PgConnection;
+use diesel::{PgConnection, RunQueryDsl};
 use diesel::r2d2::ConnectionManager;
+use diesel::sql_types::BigInt;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
 use r2d2::{ManageConnection, Pool};
 
 use crate::config::DbConfig;
+use crate::data::blocking::with_connection;
+use crate::data::data_errors::DataError;
+use crate::data::db::posts_db::render_post_bodies;
+use crate::data::migrations::{execute, Backfill, Migrate, MigrationCommand};
 use crate::data::repo_trait::DataRepository;
+use crate::metrics::PoolMetrics;
+
+pub(super) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Key of the session level advisory lock held while migrating, any constant shared by all
+/// replicas works.
+const MIGRATION_LOCK: i64 = 0x626c_6f67_6d69_6772;
+
+/// Posts written before bodies were rendered on write get rendered before the columns
+/// become `NOT NULL`.
+const BACKFILLS: &[Backfill<PgConnection>] = &[("20240930090000", render_post_bodies)];
 
 #[derive(Clone)]
 pub struct Postgres {
@@ -12,12 +29,32 @@ pub struct Postgres {
 
 impl Postgres {
     pub fn new(conf: &DbConfig) -> Result<Self, anyhow::Error> {
-        let manager = ConnectionManager::<PgConnection>::new(&conf.url);
+        let manager = ConnectionManager::<PgConnection>::new(conf.url()?);
         manager.connect()?;
-        let pool = Pool::builder().build(manager)?;
+        let pool = Pool::builder().event_handler(Box::new(PoolMetrics)).build(manager)?;
 
         Ok(Postgres { pool })
     }
+
+    pub(super) async fn run<T, F>(&self, f: F) -> Result<T, DataError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T, DataError> + Send + 'static,
+    {
+        with_connection(&self.pool, f).await
+    }
 }
 
 impl DataRepository for Postgres {}
+
+impl Migrate for Postgres {
+    fn migrate(&self, command: MigrationCommand) -> Result<Vec<String>, anyhow::Error> {
+        let mut conn = self.pool.get()?;
+
+        diesel::sql_query("SELECT pg_advisory_lock($1)").bind::<BigInt, _>(MIGRATION_LOCK).execute(&mut conn)?;
+        let result = execute(&mut *conn, MIGRATIONS, BACKFILLS, command);
+        diesel::sql_query("SELECT pg_advisory_unlock($1)").bind::<BigInt, _>(MIGRATION_LOCK).execute(&mut conn)?;
+
+        result
+    }
+}
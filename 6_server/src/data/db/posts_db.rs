@@ -1,22 +1,43 @@
+use std::str::FromStr;
+
 use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::data::data_errors::DataError;
+use crate::data::filters::prefix_pattern;
 use crate::data::db::postgres::Postgres;
-use crate::data::db::schema::posts;
+use crate::data::db::schema::{post_slugs, post_tags, posts, tags};
 use crate::data::db::schema::posts::dsl::*;
+use crate::data::db::revisions_db::record_revision;
+use crate::data::db::tags_db::{load_tags, set_tags};
 use crate::data::repositories::post_repository::PostRepository;
-use crate::models::post::{CreatePost, Post, PostFilter, Title, UpdatePost};
+use crate::models::markdown;
+use crate::models::page::Page;
+use crate::models::slug::unique_post_slug;
+use crate::models::post::{CreatePost, Post, PostCursor, PostFilter, PostSort, PostStatus, SortOrder, Title, UpdatePost};
+use crate::models::tag::TagMatch;
 
-#[derive(Insertable, Queryable)]
+#[derive(Queryable, Selectable)]
 #[diesel(table_name = posts)]
-struct DbPost {
+pub(super) struct DbPost {
     id: Uuid,
     title: String,
+    slug: String,
     body: String,
-    published: bool,
+    status: String,
     author_id: Uuid,
+    deleted_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    publish_at: Option<DateTime<Utc>>,
+    published_at: Option<DateTime<Utc>>,
+    body_html: String,
+    excerpt: String,
+    reading_time_minutes: i32,
 }
 
 impl TryFrom<DbPost> for Post {
@@ -25,10 +46,20 @@ impl TryFrom<DbPost> for Post {
     fn try_from(value: DbPost) -> Result<Self, Self::Error> {
         Ok(Post {
             id: value.id,
-            title: Title::new(value.title)?,
+            title: Title::try_new(value.title)?,
+            slug: value.slug,
             body: value.body,
-            published: value.published,
+            body_html: value.body_html,
+            excerpt: value.excerpt,
+            reading_time_minutes: value.reading_time_minutes,
+            status: PostStatus::from_str(&value.status)?,
             author: value.author_id,
+            tags: vec![],
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            publish_at: value.publish_at,
+            published_at: value.published_at,
+            deleted_at: value.deleted_at,
         })
     }
 }
@@ -37,14 +68,25 @@ impl TryFrom<DbPost> for Post {
 #[diesel(table_name = posts)]
 struct DbCreatePost {
     title: String,
+    slug: String,
     body: String,
+    body_html: String,
+    excerpt: String,
+    reading_time_minutes: i32,
+    author_id: Uuid,
 }
 
-impl From<CreatePost> for DbCreatePost {
-    fn from(post: CreatePost) -> Self {
+impl DbCreatePost {
+    fn new(author: Uuid, post_slug: String, post: CreatePost) -> Self {
+        let rendered = markdown::render(&post.body);
         DbCreatePost {
             title: post.title.to_string(),
+            slug: post_slug,
+            body_html: rendered.html,
+            excerpt: rendered.excerpt,
+            reading_time_minutes: rendered.reading_time_minutes,
             body: post.body,
+            author_id: author,
         }
     }
 }
@@ -53,63 +95,397 @@ impl From<CreatePost> for DbCreatePost {
 #[diesel(table_name = posts)]
 struct DbUpdatePost {
     title: Option<String>,
+    slug: Option<String>,
     body: Option<String>,
-    published: Option<bool>,
+    body_html: Option<String>,
+    excerpt: Option<String>,
+    reading_time_minutes: Option<i32>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl DbUpdatePost {
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.body.is_none()
+    }
 }
 
 impl From<UpdatePost> for DbUpdatePost {
     fn from(post: UpdatePost) -> Self {
+        let rendered = post.body.as_deref().map(markdown::render);
+
         DbUpdatePost {
             title: post.title.map(|t| t.to_string()),
+            slug: None,
+            body_html: rendered.as_ref().map(|r| r.html.clone()),
+            excerpt: rendered.as_ref().map(|r| r.excerpt.clone()),
+            reading_time_minutes: rendered.map(|r| r.reading_time_minutes),
             body: post.body,
-            published: post.published,
+            updated_at: None,
+        }
+    }
+}
+
+pub(super) fn into_posts(conn: &mut PgConnection, rows: Vec<DbPost>) -> Result<Vec<Post>, DataError> {
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut tags_by_post = load_tags(conn, &ids)?;
+
+    rows.into_iter()
+        .map(|row| Post::try_from(row).map(|post| Post {
+            tags: tags_by_post.remove(&post.id).unwrap_or_default(),
+            ..post
+        }))
+        .collect::<Result<Vec<Post>, Error>>().map_err(|e| e.into())
+}
+
+/// Renders the posts written before bodies were rendered on write. Runs before the migration
+/// that makes the rendered columns `NOT NULL`, with the `updated_at` trigger off since the
+/// posts themselves do not change.
+pub(super) fn render_post_bodies(conn: &mut PgConnection) -> Result<(), anyhow::Error> {
+    conn.transaction(|conn| {
+        diesel::sql_query("ALTER TABLE posts DISABLE TRIGGER set_updated_at").execute(conn)?;
+
+        let unrendered = posts
+            .filter(body_html.is_null().or(excerpt.is_null()).or(reading_time_minutes.is_null()))
+            .select((id, body))
+            .load::<(Uuid, String)>(conn)?;
+        for (post_id, post_body) in unrendered {
+            let rendered = markdown::render(&post_body);
+            diesel::update(posts.find(post_id))
+                .set((
+                    body_html.eq(rendered.html),
+                    excerpt.eq(rendered.excerpt),
+                    reading_time_minutes.eq(rendered.reading_time_minutes),
+                ))
+                .execute(conn)?;
+        }
+
+        diesel::sql_query("ALTER TABLE posts ENABLE TRIGGER set_updated_at").execute(conn)?;
+        Ok(())
+    })
+}
+
+fn into_post(conn: &mut PgConnection, row: DbPost) -> Result<Post, DataError> {
+    into_posts(conn, vec![row])?.pop().ok_or(DataError::NotFound)
+}
+
+/// Derives a slug from `post_title` that is not used, currently or previously, by any other post.
+fn unique_slug(conn: &mut PgConnection, post_title: &Title, post_id: Option<Uuid>) -> Result<String, DataError> {
+    let other = post_id.unwrap_or(Uuid::nil());
+
+    unique_post_slug(post_title, |candidate| {
+        Ok(diesel::select(diesel::dsl::exists(
+            posts.filter(slug.eq(candidate)).filter(id.ne(other))
+        )).get_result::<bool>(conn)? || diesel::select(diesel::dsl::exists(
+            post_slugs::table.filter(post_slugs::slug.eq(candidate)).filter(post_slugs::post_id.ne(other))
+        )).get_result::<bool>(conn)?)
+    })
+}
+
+/// Updates a post within the caller's transaction, recording a revision whenever title or body change.
+pub(super) fn apply_update(conn: &mut PgConnection, post_id: Uuid, editor: Uuid, mut update_post: UpdatePost) -> Result<Post, DataError> {
+    let current = posts.find(post_id).filter(deleted_at.is_null())
+        .select(DbPost::as_select())
+        .for_update()
+        .get_result::<DbPost>(conn)?;
+
+    let new_tags = update_post.tags.take();
+    let new_slug = match &update_post.title {
+        Some(new_title) => Some(unique_slug(conn, new_title, Some(post_id))?),
+        None => None,
+    }.filter(|new_slug| *new_slug != current.slug);
+    let mut changes = DbUpdatePost::from(update_post);
+    let content_changed = !changes.is_empty();
+
+    if let Some(new_slug) = &new_slug {
+        diesel::insert_into(post_slugs::table)
+            .values((post_slugs::slug.eq(&current.slug), post_slugs::post_id.eq(post_id)))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+        diesel::delete(post_slugs::table.filter(post_slugs::slug.eq(new_slug)))
+            .execute(conn)?;
+    }
+    changes.slug = new_slug;
+
+    if let Some(names) = &new_tags {
+        set_tags(conn, post_id, names)?;
+    }
+
+    if !content_changed {
+        match new_tags {
+            None => return into_post(conn, current),
+            Some(_) => changes.updated_at = Some(Utc::now()),
         }
     }
+
+    let row = diesel::update(posts.find(post_id))
+        .set(changes)
+        .returning(DbPost::as_returning())
+        .get_result::<DbPost>(conn)?;
+
+    if content_changed {
+        record_revision(conn, post_id, Some(editor), &row.title, &row.body)?;
+    }
+
+    into_post(conn, row)
 }
 
+#[async_trait]
 impl PostRepository for Postgres {
-    fn create_post(&self, create_post: CreatePost) -> Result<Post, DataError> {
-        let conn = &mut self.pool.get()?;
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_post(&self, author: Uuid, create_post: CreatePost) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            conn.transaction::<_, DataError, _>(|conn| {
+                let tag_names = create_post.tags.clone();
+                let post_slug = unique_slug(conn, &create_post.title, None)?;
+
+                let row = diesel::insert_into(posts)
+                    .values(DbCreatePost::new(author, post_slug, create_post))
+                    .returning(DbPost::as_returning())
+                    .get_result::<DbPost>(conn)?;
 
-        Ok(diesel::insert_into(posts)
-            .values(DbCreatePost::from(create_post))
-            .get_result::<DbPost>(conn)?.try_into()?)
+                set_tags(conn, row.id, &tag_names)?;
+                record_revision(conn, row.id, Some(author), &row.title, &row.body)?;
+                into_post(conn, row)
+            })
+        }).await
     }
 
-    fn get_post(&self, search_id: Uuid) -> Result<Post, DataError> {
-        let conn = &mut self.pool.get()?;
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_post(&self, search_id: Uuid) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            let row = posts.find(search_id)
+                .filter(deleted_at.is_null())
+                .select(DbPost::as_select())
+                .get_result::<DbPost>(conn)?;
 
-        Ok(posts.find(search_id)
-            .get_result::<DbPost>(conn)?.try_into()?)
+            into_post(conn, row)
+        }).await
     }
 
-    fn get_posts(&self, post_filter: PostFilter) -> Result<Vec<Post>, DataError> {
-        let conn = &mut self.pool.get()?;
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_post_by_slug(&self, search_slug: &str) -> Result<Post, DataError> {
+        let search_slug = search_slug.to_string();
 
-        let mut query = posts.into_boxed();
+        self.run(move |conn| {
+            let row = posts
+                .filter(slug.eq(&search_slug).or(id.eq_any(post_slugs::table
+                    .filter(post_slugs::slug.eq(&search_slug))
+                    .select(post_slugs::post_id))))
+                .filter(deleted_at.is_null())
+                .select(DbPost::as_select())
+                .get_result::<DbPost>(conn)?;
 
-        if let Some(q_title) = post_filter.title {
-            query = query.filter(title.like(format!("{}%", q_title)));
-        }
+            into_post(conn, row)
+        }).await
+    }
 
-        if let Some(p) = post_filter.published {
-            query = query.filter(published.eq(p));
-        }
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_posts(&self, post_filter: PostFilter) -> Result<Page<Post>, DataError> {
+        self.run(move |conn| {
+            let page_size = post_filter.page_size();
+            let (sort, order) = (post_filter.sort, post_filter.order);
+            let cursor = post_filter.cursor.as_deref()
+                .map(|c| PostCursor::decode(c).filter(|c| c.sort == sort).ok_or(DataError::InvalidCursor))
+                .transpose()?;
+            let tag_names: Vec<String> = post_filter.tag_names().iter().map(|n| n.to_string()).collect();
 
-        if let Some(a) = post_filter.author {
-            query = query.filter(author_id.eq(a));
-        }
+            let mut query = posts
+                .filter(deleted_at.is_null())
+                .select(DbPost::as_select())
+                .into_boxed();
+
+            if sort == PostSort::PublishedAt {
+                query = query.filter(published_at.is_not_null());
+            }
+
+            if let Some(q_title) = post_filter.title {
+                query = query.filter(title.ilike(prefix_pattern(&q_title)));
+            }
+
+            if let Some(s) = post_filter.status {
+                query = query.filter(status.eq(s.as_str()));
+            }
+
+            if let Some(a) = post_filter.author {
+                query = query.filter(author_id.eq(a));
+            }
+
+            if let Some(after) = post_filter.created_after {
+                query = query.filter(created_at.ge(after));
+            }
 
-        query.get_results(conn)?
-            .into_iter()
-            .map(DbPost::try_into).collect::<Result<Vec<Post>, Error>>().map_err(|e| e.into())
+            if let Some(before) = post_filter.created_before {
+                query = query.filter(created_at.lt(before));
+            }
+
+            if !tag_names.is_empty() {
+                match post_filter.tag_match {
+                    TagMatch::Any => {
+                        query = query.filter(id.eq_any(post_tags::table
+                            .inner_join(tags::table)
+                            .filter(tags::name.eq_any(tag_names))
+                            .select(post_tags::post_id)));
+                    }
+                    TagMatch::All => {
+                        for tag_name in tag_names {
+                            query = query.filter(id.eq_any(post_tags::table
+                                .inner_join(tags::table)
+                                .filter(tags::name.eq(tag_name))
+                                .select(post_tags::post_id)));
+                        }
+                    }
+                }
+            }
+
+            query = match (sort, order) {
+                (PostSort::CreatedAt, SortOrder::Asc) => query.order((created_at.asc(), id.asc())),
+                (PostSort::CreatedAt, SortOrder::Desc) => query.order((created_at.desc(), id.desc())),
+                (PostSort::PublishedAt, SortOrder::Asc) => query.order((published_at.asc(), id.asc())),
+                (PostSort::PublishedAt, SortOrder::Desc) => query.order((published_at.desc(), id.desc())),
+                (PostSort::Title, SortOrder::Asc) => query.order((title.asc(), id.asc())),
+                (PostSort::Title, SortOrder::Desc) => query.order((title.desc(), id.desc())),
+            };
+
+            if let Some(cursor) = cursor {
+                query = match sort {
+                    PostSort::CreatedAt => {
+                        let key = DateTime::parse_from_rfc3339(&cursor.key)
+                            .map_err(|_| DataError::InvalidCursor)?
+                            .with_timezone(&Utc);
+                        match order {
+                            SortOrder::Asc => query.filter(created_at.gt(key).or(created_at.eq(key).and(id.gt(cursor.id)))),
+                            SortOrder::Desc => query.filter(created_at.lt(key).or(created_at.eq(key).and(id.lt(cursor.id)))),
+                        }
+                    }
+                    PostSort::PublishedAt => {
+                        let key = DateTime::parse_from_rfc3339(&cursor.key)
+                            .map_err(|_| DataError::InvalidCursor)?
+                            .with_timezone(&Utc);
+                        match order {
+                            SortOrder::Asc => query.filter(published_at.gt(key).or(published_at.eq(key).and(id.gt(cursor.id)))),
+                            SortOrder::Desc => query.filter(published_at.lt(key).or(published_at.eq(key).and(id.lt(cursor.id)))),
+                        }
+                    }
+                    PostSort::Title => match order {
+                        SortOrder::Asc => query.filter(title.gt(cursor.key.clone()).or(title.eq(cursor.key).and(id.gt(cursor.id)))),
+                        SortOrder::Desc => query.filter(title.lt(cursor.key.clone()).or(title.eq(cursor.key).and(id.lt(cursor.id)))),
+                    },
+                };
+            }
+
+            let rows = query.limit(page_size + 1).get_results(conn)?;
+            let mut items = into_posts(conn, rows)?;
+
+            let next_cursor = if items.len() as i64 > page_size {
+                items.truncate(page_size as usize);
+                items.last().map(|last| PostCursor::after(last, sort).encode())
+            } else {
+                None
+            };
+
+            Ok(Page { items, next_cursor })
+        }).await
     }
 
-    fn update_post(&self, post_id: uuid::Uuid, update_post: UpdatePost) -> Result<Post, DataError> {
-        let conn = &mut self.pool.get()?;
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_post(&self, post_id: Uuid, editor: Uuid, update_post: UpdatePost) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            conn.transaction::<_, DataError, _>(|conn| apply_update(conn, post_id, editor, update_post))
+        }).await
+    }
 
-        diesel::update(posts).filter(id.eq(post_id))
-            .set(DbUpdatePost::from(update_post))
-            .get_result::<DbPost>(conn)?.try_into().map_err(|e: anyhow::Error| e.into())
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_post(&self, post_id: Uuid) -> Result<(), DataError> {
+        self.run(move |conn| {
+            match diesel::update(posts).filter(id.eq(post_id)).filter(deleted_at.is_null())
+                .set(deleted_at.eq(Utc::now()))
+                .execute(conn)? {
+                0 => Err(DataError::NotFound),
+                _ => Ok(()),
+            }
+        }).await
     }
-}
\ No newline at end of file
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_deleted_posts(&self) -> Result<Vec<Post>, DataError> {
+        self.run(move |conn| {
+            let rows = posts
+                .filter(deleted_at.is_not_null())
+                .order(deleted_at.desc())
+                .select(DbPost::as_select())
+                .get_results(conn)?;
+
+            into_posts(conn, rows)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn restore_post(&self, post_id: Uuid) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            let row = diesel::update(posts).filter(id.eq(post_id)).filter(deleted_at.is_not_null())
+                .set(deleted_at.eq(None::<DateTime<Utc>>))
+                .returning(DbPost::as_returning())
+                .get_result::<DbPost>(conn)?;
+
+            into_post(conn, row)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn transition_post(&self, post_id: Uuid, next: PostStatus, next_publish_at: Option<DateTime<Utc>>) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            conn.transaction::<_, DataError, _>(|conn| {
+                let current = posts.find(post_id).filter(deleted_at.is_null())
+                    .select(DbPost::as_select())
+                    .for_update()
+                    .get_result::<DbPost>(conn)?;
+
+                let from = PostStatus::from_str(&current.status)?;
+                if !from.can_transition_to(next) {
+                    return Err(DataError::InvalidTransition { from, to: next });
+                }
+
+                let next_published_at = match next {
+                    PostStatus::Published => Some(Utc::now()),
+                    PostStatus::Archived => current.published_at,
+                    _ => None,
+                };
+
+                let row = diesel::update(posts.find(post_id))
+                    .set((
+                        status.eq(next.as_str()),
+                        publish_at.eq(next_publish_at.filter(|_| next == PostStatus::Scheduled)),
+                        published_at.eq(next_published_at),
+                    ))
+                    .returning(DbPost::as_returning())
+                    .get_result::<DbPost>(conn)?;
+
+                into_post(conn, row)
+            })
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn publish_scheduled_posts(&self, now: DateTime<Utc>) -> Result<usize, DataError> {
+        self.run(move |conn| {
+            Ok(diesel::update(posts)
+                .filter(status.eq(PostStatus::Scheduled.as_str()))
+                .filter(publish_at.le(now))
+                .filter(deleted_at.is_null())
+                .set((
+                    status.eq(PostStatus::Published.as_str()),
+                    published_at.eq(publish_at),
+                    publish_at.eq(None::<DateTime<Utc>>),
+                ))
+                .execute(conn)?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn purge_deleted_posts(&self, deleted_before: DateTime<Utc>) -> Result<usize, DataError> {
+        self.run(move |conn| {
+            Ok(diesel::delete(posts.filter(deleted_at.lt(deleted_before)))
+                .execute(conn)?)
+        }).await
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::dsl::max;
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::db::posts_db::apply_update;
+use crate::data::db::postgres::Postgres;
+use crate::data::db::schema::post_revisions;
+use crate::data::db::schema::post_revisions::dsl::*;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::models::post::{Post, Title, UpdatePost};
+use crate::models::revision::PostRevision;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = post_revisions)]
+struct DbPostRevision {
+    post_id: Uuid,
+    revision: i32,
+    title: String,
+    body: String,
+    editor_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbPostRevision> for PostRevision {
+    type Error = Error;
+
+    fn try_from(value: DbPostRevision) -> Result<Self, Self::Error> {
+        Ok(PostRevision {
+            post_id: value.post_id,
+            revision: value.revision,
+            title: Title::try_new(value.title)?,
+            body: value.body,
+            editor: value.editor_id,
+            created_at: value.created_at,
+        })
+    }
+}
+
+/// Appends the next revision of a post. Callers hold the post's row lock, which keeps
+/// revision numbers gapless and unique.
+pub(super) fn record_revision(conn: &mut PgConnection, post: Uuid, editor: Option<Uuid>, new_title: &str, new_body: &str) -> Result<(), DataError> {
+    let latest = post_revisions
+        .filter(post_id.eq(post))
+        .select(max(revision))
+        .get_result::<Option<i32>>(conn)?;
+
+    diesel::insert_into(post_revisions)
+        .values((
+            post_id.eq(post),
+            revision.eq(latest.unwrap_or(0) + 1),
+            title.eq(new_title),
+            body.eq(new_body),
+            editor_id.eq(editor),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl RevisionRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_revisions(&self, post: Uuid) -> Result<Vec<PostRevision>, DataError> {
+        self.run(move |conn| {
+            post_revisions
+                .filter(post_id.eq(post))
+                .order(revision.desc())
+                .select(DbPostRevision::as_select())
+                .get_results(conn)?
+                .into_iter()
+                .map(DbPostRevision::try_into).collect::<Result<Vec<PostRevision>, Error>>().map_err(|e| e.into())
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_revision(&self, post: Uuid, number: i32) -> Result<PostRevision, DataError> {
+        self.run(move |conn| {
+            Ok(post_revisions.find((post, number))
+                .select(DbPostRevision::as_select())
+                .get_result::<DbPostRevision>(conn)?.try_into()?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn rollback_post(&self, post: Uuid, number: i32, editor: Uuid) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            conn.transaction::<_, DataError, _>(|conn| {
+                let target: PostRevision = post_revisions.find((post, number))
+                    .select(DbPostRevision::as_select())
+                    .get_result::<DbPostRevision>(conn)?.try_into()?;
+
+                apply_update(conn, post, editor, UpdatePost {
+                    title: Some(target.title),
+                    body: Some(target.body),
+                    tags: None,
+                })
+            })
+        }).await
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Float, Text};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::db::posts_db::{into_posts, DbPost};
+use crate::data::db::postgres::Postgres;
+use crate::data::db::schema::posts;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::models::post::Post;
+use crate::models::search::{SearchQuery, SearchResult};
+
+const SEARCH_POSTS: &str = r#"
+SELECT p.id,
+       ts_rank(p.search_vector, q) AS rank,
+       ts_headline('english',
+                   replace(replace(replace(p.body, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'),
+                   q,
+                   'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2') AS snippet
+FROM posts p,
+     websearch_to_tsquery('english', $1) q
+WHERE p.deleted_at IS NULL
+  AND p.status = 'published'
+  AND p.search_vector @@ q
+ORDER BY rank DESC, p.created_at DESC, p.id
+LIMIT $2
+"#;
+
+#[derive(QueryableByName)]
+struct DbSearchHit {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    id: Uuid,
+    #[diesel(sql_type = Float)]
+    rank: f32,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+}
+
+#[async_trait]
+impl SearchRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn search_posts(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DataError> {
+        self.run(move |conn| {
+            let hits = diesel::sql_query(SEARCH_POSTS)
+                .bind::<Text, _>(query.q.as_str())
+                .bind::<BigInt, _>(query.page_size())
+                .load::<DbSearchHit>(conn)?;
+
+            let rows = posts::table
+                .filter(posts::id.eq_any(hits.iter().map(|hit| hit.id).collect::<Vec<_>>()))
+                .select(DbPost::as_select())
+                .load::<DbPost>(conn)?;
+
+            let mut found: HashMap<Uuid, Post> = into_posts(conn, rows)?
+                .into_iter()
+                .map(|post| (post.id, post))
+                .collect();
+
+            Ok(hits.into_iter()
+                .filter_map(|hit| found.remove(&hit.id).map(|post| SearchResult {
+                    post,
+                    rank: hit.rank,
+                    snippet: hit.snippet,
+                }))
+                .collect())
+        }).await
+    }
+}
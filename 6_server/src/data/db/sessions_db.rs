@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+
+use crate::data::data_errors::DataError;
+use crate::data::db::postgres::Postgres;
+use crate::data::db::schema::{sessions, users};
+use crate::data::db::schema::sessions::dsl::*;
+use crate::data::db::users_db::DbUser;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::models::auth::{CreateSession, Session};
+use crate::models::user::User;
+
+#[derive(Insertable)]
+#[diesel(table_name = sessions)]
+struct DbCreateSession {
+    token_hash: String,
+    user_id: uuid::Uuid,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<CreateSession> for DbCreateSession {
+    fn from(session: CreateSession) -> Self {
+        DbCreateSession {
+            token_hash: session.token_hash,
+            user_id: session.user_id,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_session(&self, create_session: CreateSession) -> Result<Session, DataError> {
+        self.run(move |conn| {
+            let (uid, expires): (uuid::Uuid, DateTime<Utc>) = diesel::insert_into(sessions)
+                .values(DbCreateSession::from(create_session))
+                .returning((user_id, expires_at))
+                .get_result(conn)?;
+
+            Ok(Session { user_id: uid, expires_at: expires })
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_session_user(&self, search_hash: &str) -> Result<User, DataError> {
+        let search_hash = search_hash.to_string();
+
+        self.run(move |conn| {
+            let user: DbUser = sessions
+                .inner_join(users::table)
+                .filter(token_hash.eq(search_hash))
+                .filter(expires_at.gt(Utc::now()))
+                .select(DbUser::as_select())
+                .get_result(conn)?;
+
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_session(&self, search_hash: &str) -> Result<(), DataError> {
+        let search_hash = search_hash.to_string();
+
+        self.run(move |conn| {
+            diesel::delete(sessions.filter(token_hash.eq(search_hash)))
+                .execute(conn)?;
+
+            Ok(())
+        }).await
+    }
+}
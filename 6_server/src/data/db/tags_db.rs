@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::db::postgres::Postgres;
+use crate::data::db::schema::{post_tags, tags};
+use crate::data::repositories::tag_repository::TagRepository;
+use crate::models::tag::{TagCount, TagName};
+
+const TAG_COUNTS: &str = r#"
+SELECT t.name, count(p.id) AS post_count
+FROM tags t
+         LEFT JOIN post_tags pt ON pt.tag_id = t.id
+         LEFT JOIN posts p ON p.id = pt.post_id AND p.deleted_at IS NULL
+GROUP BY t.name
+ORDER BY post_count DESC, t.name
+"#;
+
+#[derive(QueryableByName)]
+struct DbTagCount {
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = BigInt)]
+    post_count: i64,
+}
+
+/// Tags of the given posts, keyed by post id. Posts without tags are absent from the map.
+pub(super) fn load_tags(conn: &mut PgConnection, post_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<TagName>>, DataError> {
+    let rows: Vec<(Uuid, String)> = post_tags::table
+        .inner_join(tags::table)
+        .filter(post_tags::post_id.eq_any(post_ids))
+        .select((post_tags::post_id, tags::name))
+        .order(tags::name.asc())
+        .load(conn)?;
+
+    let mut by_post: HashMap<Uuid, Vec<TagName>> = HashMap::new();
+    for (post_id, name) in rows {
+        by_post.entry(post_id).or_default().push(TagName::try_new(name).map_err(anyhow::Error::from)?);
+    }
+
+    Ok(by_post)
+}
+
+/// Replaces the tags of a post, creating tags that do not exist yet.
+pub(super) fn set_tags(conn: &mut PgConnection, post_id: Uuid, names: &[TagName]) -> Result<(), DataError> {
+    diesel::delete(post_tags::table.filter(post_tags::post_id.eq(post_id)))
+        .execute(conn)?;
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+
+    diesel::insert_into(tags::table)
+        .values(names.iter().map(|n| tags::name.eq(n)).collect::<Vec<_>>())
+        .on_conflict(tags::name)
+        .do_nothing()
+        .execute(conn)?;
+
+    let tag_ids: Vec<Uuid> = tags::table
+        .filter(tags::name.eq_any(&names))
+        .select(tags::id)
+        .load(conn)?;
+
+    diesel::insert_into(post_tags::table)
+        .values(tag_ids.into_iter()
+            .map(|tag_id| (post_tags::post_id.eq(post_id), post_tags::tag_id.eq(tag_id)))
+            .collect::<Vec<_>>())
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl TagRepository for Postgres {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_tags(&self) -> Result<Vec<TagCount>, DataError> {
+        self.run(move |conn| {
+            diesel::sql_query(TAG_COUNTS)
+                .load::<DbTagCount>(conn)?
+                .into_iter()
+                .map(|row| Ok(TagCount {
+                    name: TagName::try_new(row.name).map_err(anyhow::Error::from)?,
+                    post_count: row.post_count,
+                }))
+                .collect()
+        }).await
+    }
+}
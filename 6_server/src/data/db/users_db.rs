@@ -1,19 +1,27 @@
 use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
+use tracing::instrument;
 
 use crate::data::data_errors::DataError;
+use crate::data::filters::prefix_pattern;
 use crate::data::db::postgres::Postgres;
-use crate::data::db::schema::users;
-use crate::data::db::schema::users::table;
+use crate::data::db::schema::{posts, users};
+use crate::data::db::schema::users::{email, password_hash, role, table, username};
 use crate::data::repositories::user_repository::UserRepository;
-use crate::models::user::{CreateUser, User, Username};
+use crate::models::auth::UserCredentials;
+use crate::models::user::{CreateUser, Role, UpdateUser, User, UserFilter, Username};
 
-#[derive(Insertable, Queryable)]
+#[derive(Queryable, Selectable)]
 #[diesel(table_name = users)]
-struct DbUser {
+pub(super) struct DbUser {
     id: uuid::Uuid,
     username: String,
     email: String,
+    role: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 impl TryFrom<DbUser> for User {
@@ -22,8 +30,11 @@ impl TryFrom<DbUser> for User {
     fn try_from(value: DbUser) -> Result<Self, Self::Error> {
         Ok(User {
             id: value.id,
-            username: Username::new(value.username)?,
+            username: Username::try_new(value.username)?,
             email: value.email.parse()?,
+            role: value.role.parse()?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
         })
     }
 }
@@ -33,29 +44,137 @@ impl TryFrom<DbUser> for User {
 struct DbCreateUser {
     username: String,
     email: String,
+    password_hash: String,
 }
 
-impl From<CreateUser> for DbCreateUser {
-    fn from(user: CreateUser) -> Self {
-        DbCreateUser {
+impl TryFrom<CreateUser> for DbCreateUser {
+    type Error = Error;
+
+    fn try_from(user: CreateUser) -> Result<Self, Self::Error> {
+        Ok(DbCreateUser {
+            password_hash: user.password.hash()?,
             username: user.name.to_string(),
             email: user.email.to_string(),
+        })
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = users)]
+struct DbUpdateUser {
+    username: Option<String>,
+    email: Option<String>,
+}
+
+impl From<UpdateUser> for DbUpdateUser {
+    fn from(user: UpdateUser) -> Self {
+        DbUpdateUser {
+            username: user.name.map(|n| n.to_string()),
+            email: user.email.map(|e| e.to_string()),
         }
     }
 }
 
+#[async_trait]
 impl UserRepository for Postgres {
-    fn create_user(&self, create_user: CreateUser) -> Result<User, DataError> {
-        let conn = &mut self.pool.get()?;
-        let user: DbUser = diesel::insert_into(table)
-            .values(DbCreateUser::from(create_user))
-            .get_result(conn)?;
-        Ok(user.try_into()?)
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_user(&self, create_user: CreateUser) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = diesel::insert_into(table)
+                .values(DbCreateUser::try_from(create_user)?)
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_user(&self, id: uuid::Uuid) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = table.find(id)
+                .select(DbUser::as_select())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
     }
 
-    fn get_user(&self, id: uuid::Uuid) -> Result<User, DataError> {
-        let conn = &mut self.pool.get()?;
-        let user: DbUser = table.find(id).get_result(conn)?;
-        Ok(user.try_into()?)
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_users(&self, user_filter: UserFilter) -> Result<Vec<User>, DataError> {
+        self.run(move |conn| {
+            let mut query = table.select(DbUser::as_select()).into_boxed();
+
+            if let Some(q_username) = user_filter.username {
+                query = query.filter(username.like(prefix_pattern(&q_username)));
+            }
+
+            if let Some(q_email) = user_filter.email {
+                query = query.filter(email.like(prefix_pattern(&q_email)));
+            }
+
+            query.order(username.asc())
+                .get_results(conn)?
+                .into_iter()
+                .map(DbUser::try_into).collect::<Result<Vec<User>, Error>>().map_err(|e| e.into())
+        }).await
     }
-}
\ No newline at end of file
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_user(&self, id: uuid::Uuid, update_user: UpdateUser) -> Result<User, DataError> {
+        if update_user.name.is_none() && update_user.email.is_none() {
+            return self.get_user(id).await;
+        }
+
+        self.run(move |conn| {
+            let user: DbUser = diesel::update(table.find(id))
+                .set(DbUpdateUser::from(update_user))
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_user(&self, id: uuid::Uuid) -> Result<(), DataError> {
+        self.run(move |conn| {
+            conn.transaction(|conn| {
+                let owns_posts = diesel::select(diesel::dsl::exists(posts::table.filter(posts::author_id.eq(id))))
+                    .get_result::<bool>(conn)?;
+                if owns_posts {
+                    return Err(DataError::UserHasPosts);
+                }
+
+                match diesel::delete(table.find(id)).execute(conn)? {
+                    0 => Err(DataError::NotFound),
+                    _ => Ok(()),
+                }
+            })
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_user_credentials(&self, name: &Username) -> Result<UserCredentials, DataError> {
+        let name = name.to_string();
+
+        self.run(move |conn| {
+            let (user, hash): (DbUser, Option<String>) = table
+                .filter(username.eq(name))
+                .select((DbUser::as_select(), password_hash))
+                .get_result(conn)?;
+            Ok(UserCredentials {
+                user: user.try_into()?,
+                password_hash: hash,
+            })
+        }).await
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_user_role(&self, id: uuid::Uuid, new_role: Role) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = diesel::update(table.find(id))
+                .set(role.eq(new_role.as_str()))
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+}
@@ -0,0 +1,9 @@
+/// Builds a `LIKE` pattern matching values starting with `value`, escaping the
+/// wildcard characters so user input is matched literally.
+pub fn prefix_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
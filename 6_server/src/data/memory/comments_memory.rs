@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::{now, InMemory};
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::models::comment::{Comment, CreateComment, ModerationStatus};
+
+#[async_trait]
+impl CommentRepository for InMemory {
+    async fn create_comment(&self, post_id: Uuid, author: Uuid, create_comment: CreateComment) -> Result<Comment, DataError> {
+        let mut store = self.write()?;
+
+        if let Some(parent) = create_comment.parent_id {
+            store.comments.iter()
+                .find(|comment| comment.id == parent && comment.post_id == post_id)
+                .ok_or(DataError::NotFound)?;
+        }
+
+        let created = now();
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            post_id,
+            author,
+            parent_id: create_comment.parent_id,
+            body: create_comment.body,
+            status: ModerationStatus::Pending,
+            created_at: created,
+            updated_at: created,
+        };
+        store.comments.push(comment.clone());
+
+        Ok(comment)
+    }
+
+    async fn get_comment(&self, id: Uuid) -> Result<Comment, DataError> {
+        let store = self.read()?;
+
+        store.comments.iter().find(|comment| comment.id == id).cloned().ok_or(DataError::NotFound)
+    }
+
+    async fn get_comments(&self, post_id: Uuid, status: Option<ModerationStatus>) -> Result<Vec<Comment>, DataError> {
+        let store = self.read()?;
+
+        Ok(store.comments.iter()
+            .filter(|comment| comment.post_id == post_id)
+            .filter(|comment| status.is_none_or(|s| comment.status == s))
+            .cloned()
+            .collect())
+    }
+
+    async fn update_comment_status(&self, id: Uuid, status: ModerationStatus) -> Result<Comment, DataError> {
+        let mut store = self.write()?;
+
+        let comment = store.comments.iter_mut().find(|comment| comment.id == id).ok_or(DataError::NotFound)?;
+        comment.status = status;
+        comment.updated_at = now();
+
+        Ok(comment.clone())
+    }
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::repositories::health_repository::HealthRepository;
+use crate::models::health::PoolStatus;
+
+#[async_trait]
+impl HealthRepository for InMemory {
+    async fn ping(&self) -> Result<(), DataError> {
+        self.read().map(|_| ())
+    }
+
+    async fn pending_migrations(&self) -> Result<Vec<String>, DataError> {
+        Ok(Vec::new())
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        None
+    }
+}
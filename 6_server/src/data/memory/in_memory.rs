@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::repo_trait::DataRepository;
+use crate::models::auth::CreateSession;
+use crate::models::comment::Comment;
+use crate::models::post::{Post, Title};
+use crate::models::revision::PostRevision;
+use crate::models::slug::unique_post_slug;
+use crate::models::tag::TagName;
+use crate::models::user::User;
+
+/// Keeps all data in process memory, mirroring the behavior of `Postgres` including its
+/// unique constraints and cascading deletes. Everything is lost when the process exits.
+#[derive(Clone, Default)]
+pub struct InMemory {
+    store: Arc<RwLock<Store>>,
+}
+
+impl InMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn read(&self) -> Result<RwLockReadGuard<'_, Store>, DataError> {
+        self.store.read().map_err(|_| DataError::InternalServerError(anyhow::anyhow!("in-memory store is poisoned")))
+    }
+
+    pub(super) fn write(&self) -> Result<RwLockWriteGuard<'_, Store>, DataError> {
+        self.store.write().map_err(|_| DataError::InternalServerError(anyhow::anyhow!("in-memory store is poisoned")))
+    }
+}
+
+impl DataRepository for InMemory {}
+
+pub(super) struct StoredUser {
+    pub user: User,
+    pub password_hash: String,
+}
+
+#[derive(Default)]
+pub(super) struct Store {
+    pub users: HashMap<Uuid, StoredUser>,
+    /// Sessions keyed by token hash.
+    pub sessions: HashMap<String, CreateSession>,
+    pub posts: HashMap<Uuid, Post>,
+    /// Previous slugs of posts.
+    pub post_slugs: HashMap<String, Uuid>,
+    /// Comments in creation order.
+    pub comments: Vec<Comment>,
+    /// Revisions of each post, oldest first.
+    pub revisions: HashMap<Uuid, Vec<PostRevision>>,
+    pub tags: HashSet<TagName>,
+}
+
+/// Current time at the microsecond precision Postgres stores, so timestamps round-trip
+/// through cursors the same way with both repositories.
+pub(super) fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(6)
+}
+
+impl Store {
+    pub fn post(&self, id: Uuid) -> Result<&Post, DataError> {
+        self.posts.get(&id).filter(|post| post.deleted_at.is_none()).ok_or(DataError::NotFound)
+    }
+
+    pub fn post_mut(&mut self, id: Uuid) -> Result<&mut Post, DataError> {
+        self.posts.get_mut(&id).filter(|post| post.deleted_at.is_none()).ok_or(DataError::NotFound)
+    }
+
+    /// Slug for `title` that is not used, currently or previously, by any post other than `post_id`.
+    pub fn unique_slug(&self, title: &Title, post_id: Option<Uuid>) -> String {
+        let other = post_id.unwrap_or(Uuid::nil());
+
+        unique_post_slug::<()>(title, |candidate| Ok(
+            self.posts.values().any(|post| post.slug == candidate && post.id != other)
+                || self.post_slugs.get(candidate).is_some_and(|owner| *owner != other)
+        )).expect("slug lookup is infallible")
+    }
+
+    /// Normalizes `names` the way the tag tables do: sorted by name and without duplicates.
+    /// Tags that do not exist yet are created.
+    pub fn set_tags(&mut self, names: &[TagName]) -> Vec<TagName> {
+        let mut names = names.to_vec();
+        names.sort_by_key(|name| name.to_string());
+        names.dedup();
+        self.tags.extend(names.iter().cloned());
+        names
+    }
+
+    pub fn record_revision(&mut self, post_id: Uuid, editor: Option<Uuid>, title: &Title, body: &str) {
+        let revisions = self.revisions.entry(post_id).or_default();
+        revisions.push(PostRevision {
+            post_id,
+            revision: revisions.last().map_or(0, |latest| latest.revision) + 1,
+            title: title.clone(),
+            body: body.to_string(),
+            editor,
+            created_at: now(),
+        });
+    }
+
+    /// Removes posts along with everything referencing them.
+    pub fn remove_posts(&mut self, ids: &HashSet<Uuid>) {
+        self.posts.retain(|id, _| !ids.contains(id));
+        self.post_slugs.retain(|_, post_id| !ids.contains(post_id));
+        self.revisions.retain(|post_id, _| !ids.contains(post_id));
+        self.comments.retain(|comment| !ids.contains(&comment.post_id));
+    }
+
+    /// Removes replies whose parent comment no longer exists, recursively.
+    pub fn remove_orphaned_comments(&mut self) {
+        loop {
+            let ids: HashSet<Uuid> = self.comments.iter().map(|comment| comment.id).collect();
+            let before = self.comments.len();
+            self.comments.retain(|comment| comment.parent_id.is_none_or(|parent| ids.contains(&parent)));
+            if self.comments.len() == before {
+                return;
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use chrono::Duration;
+    use email_address::EmailAddress;
+
+    use crate::data::repositories::post_repository::PostRepository;
+    use crate::data::repositories::search_repository::SearchRepository;
+    use crate::data::repositories::user_repository::UserRepository;
+    use crate::models::auth::Password;
+    use crate::models::post::{CreatePost, PostCursor, PostFilter, PostSort, PostStatus, SortOrder, UpdatePost};
+    use crate::models::search::SearchQuery;
+    use crate::models::user::{CreateUser, Username};
+
+    use super::*;
+
+    async fn create_user(repo: &InMemory, name: &str, email: &str) -> Result<User, DataError> {
+        repo.create_user(CreateUser {
+            name: Username::try_new(name).unwrap(),
+            email: EmailAddress::from_str(email).unwrap(),
+            password: Password::try_new("supersecret").unwrap(),
+        }).await
+    }
+
+    async fn create_post(repo: &InMemory, author: Uuid, title: &str, tags: &[&str]) -> Post {
+        repo.create_post(author, CreatePost {
+            title: Title::try_new(title).unwrap(),
+            body: "body".to_string(),
+            tags: tags.iter().map(|name| TagName::try_new(*name).unwrap()).collect(),
+        }).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_user() {
+        let repo = InMemory::new();
+        create_user(&repo, "alice", "alice@test.com").await.unwrap();
+
+        assert!(matches!(create_user(&repo, "alice", "other@test.com").await, Err(DataError::Duplicate)));
+        assert!(matches!(create_user(&repo, "other", "alice@test.com").await, Err(DataError::Duplicate)));
+        assert!(matches!(repo.delete_user(Uuid::nil()).await, Err(DataError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_filters_and_paginates() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        for title in ["Rust basics", "Rust traits", "Go basics"] {
+            create_post(&repo, author, title, &["rust"]).await;
+        }
+        create_post(&repo, author, "Untagged", &[]).await;
+
+        let filter = PostFilter {
+            title: Some("rust".to_string()),
+            tags: Some("rust".to_string()),
+            limit: Some(1),
+            sort: PostSort::Title,
+            order: SortOrder::Asc,
+            ..PostFilter::default()
+        };
+        let first = repo.get_posts(filter.clone()).await.unwrap();
+        assert_eq!(first.items[0].title.as_ref(), "Rust basics");
+
+        let second = repo.get_posts(PostFilter { cursor: first.next_cursor, ..filter.clone() }).await.unwrap();
+        assert_eq!(second.items[0].title.as_ref(), "Rust traits");
+        assert_eq!(second.next_cursor, None);
+
+        let wrong_sort = PostFilter { cursor: Some(PostCursor::after(&second.items[0], PostSort::CreatedAt).encode()), ..filter };
+        assert!(matches!(repo.get_posts(wrong_sort).await, Err(DataError::InvalidCursor)));
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_by_published_at() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let older = create_post(&repo, author, "Written first", &[]).await;
+        let newer = create_post(&repo, author, "Written second", &[]).await;
+        create_post(&repo, author, "Still a draft", &[]).await;
+        for post in [&newer, &older] {
+            repo.transition_post(post.id, PostStatus::InReview, None).await.unwrap();
+            repo.transition_post(post.id, PostStatus::Published, None).await.unwrap();
+        }
+
+        let page = repo.get_posts(PostFilter { sort: PostSort::PublishedAt, limit: Some(1), ..PostFilter::default() }).await.unwrap();
+        assert_eq!(page.items[0].id, older.id);
+
+        let next = repo.get_posts(PostFilter { sort: PostSort::PublishedAt, cursor: page.next_cursor, ..PostFilter::default() }).await.unwrap();
+        assert_eq!(next.items.iter().map(|post| post.id).collect::<Vec<_>>(), vec![newer.id]);
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_created_between() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let bound = DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        for (title, offset) in [("Just before", -1), ("Exactly at", 0), ("Just after", 1)] {
+            let post = create_post(&repo, author, title, &[]).await;
+            repo.write().unwrap().post_mut(post.id).unwrap().created_at = bound + Duration::seconds(offset);
+        }
+
+        let titles = |after: Option<DateTime<Utc>>, before: Option<DateTime<Utc>>| {
+            let filter = PostFilter { created_after: after, created_before: before, order: SortOrder::Asc, ..PostFilter::default() };
+            let repo = repo.clone();
+            async move {
+                repo.get_posts(filter).await.unwrap().items.into_iter().map(|post| post.title.into_inner()).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(titles(Some(bound), None).await, vec!["Exactly at", "Just after"]);
+        assert_eq!(titles(None, Some(bound)).await, vec!["Just before"]);
+        assert_eq!(titles(Some(bound - Duration::seconds(1)), Some(bound + Duration::seconds(1))).await, vec!["Just before", "Exactly at"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_post_keeps_slug_history() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let post = create_post(&repo, author, "First title", &[]).await;
+
+        let updated = repo.update_post(post.id, author, UpdatePost {
+            title: Some(Title::try_new("Second title").unwrap()),
+            body: None,
+            tags: None,
+        }).await.unwrap();
+
+        assert_eq!(updated.slug, "second-title");
+        assert_eq!(repo.get_post_by_slug("first-title").await.unwrap().id, post.id);
+        assert_eq!(create_post(&repo, author, "First title", &[]).await.slug, "first-title-2");
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_keeps_posts() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let post = create_post(&repo, author, "Doomed", &[]).await;
+
+        assert!(matches!(repo.delete_user(author).await, Err(DataError::UserHasPosts)));
+        repo.delete_post(post.id).await.unwrap();
+        assert!(matches!(repo.delete_user(author).await, Err(DataError::UserHasPosts)));
+        assert!(repo.restore_post(post.id).await.is_ok());
+
+        repo.delete_post(post.id).await.unwrap();
+        repo.purge_deleted_posts(now() + Duration::seconds(1)).await.unwrap();
+        repo.delete_user(author).await.unwrap();
+
+        assert!(matches!(repo.get_user(author).await, Err(DataError::NotFound)));
+        assert!(repo.read().unwrap().revisions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_skips_unpublished_posts() {
+        let repo = InMemory::new();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        create_post(&repo, author, "Rust draft", &[]).await;
+        let published = create_post(&repo, author, "Rust published", &[]).await;
+        repo.transition_post(published.id, PostStatus::InReview, None).await.unwrap();
+        repo.transition_post(published.id, PostStatus::Published, None).await.unwrap();
+
+        let hits = repo.search_posts(SearchQuery { q: "rust".to_string(), limit: None }).await.unwrap();
+
+        assert_eq!(hits.iter().map(|hit| hit.post.id).collect::<Vec<_>>(), vec![published.id]);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod in_memory;
+mod users_memory;
+mod posts_memory;
+mod sessions_memory;
+mod search_memory;
+mod comments_memory;
+mod tags_memory;
+mod revisions_memory;
+mod health_memory;
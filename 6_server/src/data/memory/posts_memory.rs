@@ -0,0 +1,268 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::{now, InMemory, Store};
+use crate::data::repositories::post_repository::PostRepository;
+use crate::models::markdown;
+use crate::models::page::Page;
+use crate::models::post::{CreatePost, Post, PostCursor, PostFilter, PostSort, PostStatus, SortOrder, UpdatePost};
+use crate::models::tag::TagMatch;
+
+impl Store {
+    /// Counterpart of `posts_db::apply_update`, recording a revision whenever title or body change.
+    pub(super) fn apply_update(&mut self, post_id: Uuid, editor: Uuid, update_post: UpdatePost) -> Result<Post, DataError> {
+        let current = self.post(post_id)?.clone();
+
+        let new_slug = update_post.title.as_ref()
+            .map(|new_title| self.unique_slug(new_title, Some(post_id)))
+            .filter(|new_slug| *new_slug != current.slug);
+        let new_tags = update_post.tags.map(|names| self.set_tags(&names));
+        let content_changed = update_post.title.is_some() || update_post.body.is_some();
+
+        if let Some(new_slug) = &new_slug {
+            self.post_slugs.entry(current.slug.clone()).or_insert(post_id);
+            self.post_slugs.remove(new_slug);
+        }
+
+        if !content_changed && new_tags.is_none() {
+            return Ok(current);
+        }
+
+        let post = self.post_mut(post_id)?;
+        if let Some(new_title) = update_post.title {
+            post.title = new_title;
+        }
+        if let Some(new_body) = update_post.body {
+            let rendered = markdown::render(&new_body);
+            post.body = new_body;
+            post.body_html = rendered.html;
+            post.excerpt = rendered.excerpt;
+            post.reading_time_minutes = rendered.reading_time_minutes;
+        }
+        if let Some(new_slug) = new_slug {
+            post.slug = new_slug;
+        }
+        if let Some(new_tags) = new_tags {
+            post.tags = new_tags;
+        }
+        post.updated_at = now();
+        let post = post.clone();
+
+        if content_changed {
+            self.record_revision(post_id, Some(editor), &post.title, &post.body);
+        }
+
+        Ok(post)
+    }
+}
+
+/// Keyset comparison of `post` against the position a cursor points at.
+fn compare_to_cursor(post: &Post, cursor: &PostCursor, key: &Option<DateTime<Utc>>) -> Ordering {
+    match (cursor.sort, key) {
+        (PostSort::CreatedAt, Some(created)) => (post.created_at, post.id).cmp(&(*created, cursor.id)),
+        (PostSort::PublishedAt, Some(published)) => (post.published_at, post.id).cmp(&(Some(*published), cursor.id)),
+        _ => (post.title.as_ref(), post.id).cmp(&(cursor.key.as_str(), cursor.id)),
+    }
+}
+
+#[async_trait]
+impl PostRepository for InMemory {
+    async fn create_post(&self, author: Uuid, create_post: CreatePost) -> Result<Post, DataError> {
+        let mut store = self.write()?;
+
+        let rendered = markdown::render(&create_post.body);
+        let created = now();
+        let post = Post {
+            id: Uuid::new_v4(),
+            slug: store.unique_slug(&create_post.title, None),
+            title: create_post.title,
+            body: create_post.body,
+            body_html: rendered.html,
+            excerpt: rendered.excerpt,
+            reading_time_minutes: rendered.reading_time_minutes,
+            status: PostStatus::Draft,
+            author,
+            tags: store.set_tags(&create_post.tags),
+            created_at: created,
+            updated_at: created,
+            publish_at: None,
+            published_at: None,
+            deleted_at: None,
+        };
+
+        store.posts.insert(post.id, post.clone());
+        store.record_revision(post.id, Some(author), &post.title, &post.body);
+
+        Ok(post)
+    }
+
+    async fn get_post(&self, id: Uuid) -> Result<Post, DataError> {
+        let store = self.read()?;
+
+        store.post(id).cloned()
+    }
+
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Post, DataError> {
+        let store = self.read()?;
+
+        let id = store.posts.values()
+            .find(|post| post.slug == slug)
+            .map(|post| post.id)
+            .or_else(|| store.post_slugs.get(slug).copied())
+            .ok_or(DataError::NotFound)?;
+
+        store.post(id).cloned()
+    }
+
+    async fn get_posts(&self, post_filter: PostFilter) -> Result<Page<Post>, DataError> {
+        let store = self.read()?;
+
+        let page_size = post_filter.page_size();
+        let (sort, order) = (post_filter.sort, post_filter.order);
+        let cursor = post_filter.cursor.as_deref()
+            .map(|c| PostCursor::decode(c).filter(|c| c.sort == sort).ok_or(DataError::InvalidCursor))
+            .transpose()?;
+        let cursor_at = match (&cursor, sort) {
+            (Some(cursor), PostSort::CreatedAt | PostSort::PublishedAt) => Some(DateTime::parse_from_rfc3339(&cursor.key)
+                .map_err(|_| DataError::InvalidCursor)?
+                .with_timezone(&Utc)),
+            _ => None,
+        };
+        let title_prefix = post_filter.title.as_deref().map(str::to_lowercase);
+        let tag_names = post_filter.tag_names();
+
+        let mut items: Vec<Post> = store.posts.values()
+            .filter(|post| post.deleted_at.is_none())
+            .filter(|post| sort != PostSort::PublishedAt || post.published_at.is_some())
+            .filter(|post| title_prefix.as_deref().is_none_or(|prefix| post.title.as_ref().to_lowercase().starts_with(prefix)))
+            .filter(|post| post_filter.status.is_none_or(|s| post.status == s))
+            .filter(|post| post_filter.author.is_none_or(|a| post.author == a))
+            .filter(|post| post_filter.created_after.is_none_or(|after| post.created_at >= after))
+            .filter(|post| post_filter.created_before.is_none_or(|before| post.created_at < before))
+            .filter(|post| tag_names.is_empty() || match post_filter.tag_match {
+                TagMatch::Any => tag_names.iter().any(|name| post.tags.contains(name)),
+                TagMatch::All => tag_names.iter().all(|name| post.tags.contains(name)),
+            })
+            .filter(|post| cursor.as_ref().is_none_or(|cursor| matches!(
+                (compare_to_cursor(post, cursor, &cursor_at), order),
+                (Ordering::Greater, SortOrder::Asc) | (Ordering::Less, SortOrder::Desc)
+            )))
+            .cloned()
+            .collect();
+
+        items.sort_by(|a, b| {
+            let ordering = match sort {
+                PostSort::CreatedAt => (a.created_at, a.id).cmp(&(b.created_at, b.id)),
+                PostSort::PublishedAt => (a.published_at, a.id).cmp(&(b.published_at, b.id)),
+                PostSort::Title => (a.title.as_ref(), a.id).cmp(&(b.title.as_ref(), b.id)),
+            };
+            match order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let next_cursor = if items.len() as i64 > page_size {
+            items.truncate(page_size as usize);
+            items.last().map(|last| PostCursor::after(last, sort).encode())
+        } else {
+            None
+        };
+
+        Ok(Page { items, next_cursor })
+    }
+
+    async fn update_post(&self, id: Uuid, editor: Uuid, update_post: UpdatePost) -> Result<Post, DataError> {
+        let mut store = self.write()?;
+
+        store.apply_update(id, editor, update_post)
+    }
+
+    async fn delete_post(&self, id: Uuid) -> Result<(), DataError> {
+        let mut store = self.write()?;
+
+        let post = store.post_mut(id)?;
+        post.deleted_at = Some(now());
+        post.updated_at = now();
+
+        Ok(())
+    }
+
+    async fn get_deleted_posts(&self) -> Result<Vec<Post>, DataError> {
+        let store = self.read()?;
+
+        let mut deleted: Vec<Post> = store.posts.values()
+            .filter(|post| post.deleted_at.is_some())
+            .cloned()
+            .collect();
+        deleted.sort_by_key(|post| Reverse(post.deleted_at));
+
+        Ok(deleted)
+    }
+
+    async fn restore_post(&self, id: Uuid) -> Result<Post, DataError> {
+        let mut store = self.write()?;
+
+        let post = store.posts.get_mut(&id)
+            .filter(|post| post.deleted_at.is_some())
+            .ok_or(DataError::NotFound)?;
+        post.deleted_at = None;
+        post.updated_at = now();
+
+        Ok(post.clone())
+    }
+
+    async fn transition_post(&self, id: Uuid, status: PostStatus, publish_at: Option<DateTime<Utc>>) -> Result<Post, DataError> {
+        let mut store = self.write()?;
+
+        let post = store.post_mut(id)?;
+        if !post.status.can_transition_to(status) {
+            return Err(DataError::InvalidTransition { from: post.status, to: status });
+        }
+
+        post.published_at = match status {
+            PostStatus::Published => Some(now()),
+            PostStatus::Archived => post.published_at,
+            _ => None,
+        };
+        post.publish_at = publish_at.filter(|_| status == PostStatus::Scheduled);
+        post.status = status;
+        post.updated_at = now();
+
+        Ok(post.clone())
+    }
+
+    async fn publish_scheduled_posts(&self, now_at: DateTime<Utc>) -> Result<usize, DataError> {
+        let mut store = self.write()?;
+
+        let updated = now();
+        let due = store.posts.values_mut()
+            .filter(|post| post.deleted_at.is_none() && post.status == PostStatus::Scheduled)
+            .filter(|post| post.publish_at.is_some_and(|at| at <= now_at))
+            .map(|post| {
+                post.status = PostStatus::Published;
+                post.published_at = post.publish_at.take();
+                post.updated_at = updated;
+            })
+            .count();
+
+        Ok(due)
+    }
+
+    async fn purge_deleted_posts(&self, deleted_before: DateTime<Utc>) -> Result<usize, DataError> {
+        let mut store = self.write()?;
+
+        let purged: HashSet<Uuid> = store.posts.values()
+            .filter(|post| post.deleted_at.is_some_and(|at| at < deleted_before))
+            .map(|post| post.id)
+            .collect();
+        store.remove_posts(&purged);
+
+        Ok(purged.len())
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::models::post::{Post, UpdatePost};
+use crate::models::revision::PostRevision;
+
+#[async_trait]
+impl RevisionRepository for InMemory {
+    async fn get_revisions(&self, post_id: Uuid) -> Result<Vec<PostRevision>, DataError> {
+        let store = self.read()?;
+
+        Ok(store.revisions.get(&post_id)
+            .map(|revisions| revisions.iter().rev().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_revision(&self, post_id: Uuid, revision: i32) -> Result<PostRevision, DataError> {
+        let store = self.read()?;
+
+        store.revisions.get(&post_id)
+            .and_then(|revisions| revisions.iter().find(|r| r.revision == revision))
+            .cloned()
+            .ok_or(DataError::NotFound)
+    }
+
+    async fn rollback_post(&self, post_id: Uuid, revision: i32, editor: Uuid) -> Result<Post, DataError> {
+        let mut store = self.write()?;
+
+        let target = store.revisions.get(&post_id)
+            .and_then(|revisions| revisions.iter().find(|r| r.revision == revision))
+            .cloned()
+            .ok_or(DataError::NotFound)?;
+
+        store.apply_update(post_id, editor, UpdatePost {
+            title: Some(target.title),
+            body: Some(target.body),
+            tags: None,
+        })
+    }
+}
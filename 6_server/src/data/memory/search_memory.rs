@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::models::post::{Post, PostStatus};
+use crate::models::search::{SearchQuery, SearchResult, SearchTerms};
+
+#[async_trait]
+impl SearchRepository for InMemory {
+    async fn search_posts(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DataError> {
+        let store = self.read()?;
+        let terms = SearchTerms::parse(&query.q);
+
+        let mut hits: Vec<(&Post, f32)> = store.posts.values()
+            .filter(|post| post.deleted_at.is_none() && post.status == PostStatus::Published)
+            .filter_map(|post| terms.rank(post).map(|rank| (post, rank)))
+            .collect();
+        hits.sort_by(|(a, a_rank), (b, b_rank)| b_rank.total_cmp(a_rank)
+            .then(b.created_at.cmp(&a.created_at))
+            .then(a.id.cmp(&b.id)));
+
+        Ok(hits.into_iter()
+            .take(query.page_size() as usize)
+            .map(|(post, rank)| SearchResult {
+                post: post.clone(),
+                rank,
+                snippet: terms.snippet(&post.body),
+            })
+            .collect())
+    }
+}
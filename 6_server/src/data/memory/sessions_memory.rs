@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::models::auth::{CreateSession, Session};
+use crate::models::user::User;
+
+#[async_trait]
+impl SessionRepository for InMemory {
+    async fn create_session(&self, create_session: CreateSession) -> Result<Session, DataError> {
+        let mut store = self.write()?;
+
+        if !store.users.contains_key(&create_session.user_id) {
+            return Err(DataError::NotFound);
+        }
+        if store.sessions.contains_key(&create_session.token_hash) {
+            return Err(DataError::Duplicate);
+        }
+
+        let session = Session { user_id: create_session.user_id, expires_at: create_session.expires_at };
+        store.sessions.insert(create_session.token_hash.clone(), create_session);
+
+        Ok(session)
+    }
+
+    async fn get_session_user(&self, token_hash: &str) -> Result<User, DataError> {
+        let store = self.read()?;
+
+        store.sessions.get(token_hash)
+            .filter(|session| session.expires_at > Utc::now())
+            .and_then(|session| store.users.get(&session.user_id))
+            .map(|stored| stored.user.clone())
+            .ok_or(DataError::NotFound)
+    }
+
+    async fn delete_session(&self, token_hash: &str) -> Result<(), DataError> {
+        let mut store = self.write()?;
+
+        store.sessions.remove(token_hash);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::repositories::tag_repository::TagRepository;
+use crate::models::tag::TagCount;
+
+#[async_trait]
+impl TagRepository for InMemory {
+    async fn get_tags(&self) -> Result<Vec<TagCount>, DataError> {
+        let store = self.read()?;
+
+        let mut counts: Vec<TagCount> = store.tags.iter()
+            .map(|name| TagCount {
+                name: name.clone(),
+                post_count: store.posts.values()
+                    .filter(|post| post.deleted_at.is_none() && post.tags.contains(name))
+                    .count() as i64,
+            })
+            .collect();
+        counts.sort_by(|a, b| b.post_count.cmp(&a.post_count).then(a.name.to_string().cmp(&b.name.to_string())));
+
+        Ok(counts)
+    }
+}
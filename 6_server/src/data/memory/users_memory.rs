@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::memory::in_memory::{now, InMemory, Store, StoredUser};
+use crate::data::repositories::user_repository::UserRepository;
+use crate::models::auth::UserCredentials;
+use crate::models::user::{CreateUser, Role, UpdateUser, User, UserFilter, Username};
+
+impl Store {
+    /// Mirrors the unique constraints on username and email.
+    fn check_unique_user(&self, id: Option<Uuid>, username: Option<&Username>, email: Option<&str>) -> Result<(), DataError> {
+        let taken = self.users.values()
+            .filter(|stored| Some(stored.user.id) != id)
+            .any(|stored| username.is_some_and(|name| stored.user.username == *name)
+                || email.is_some_and(|email| stored.user.email.as_str() == email));
+
+        match taken {
+            true => Err(DataError::Duplicate),
+            false => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemory {
+    async fn create_user(&self, create_user: CreateUser) -> Result<User, DataError> {
+        let password_hash = create_user.password.hash_blocking().await?;
+        let mut store = self.write()?;
+
+        store.check_unique_user(None, Some(&create_user.name), Some(create_user.email.as_str()))?;
+
+        let created = now();
+        let user = User {
+            id: Uuid::new_v4(),
+            username: create_user.name,
+            email: create_user.email,
+            role: Role::Reader,
+            created_at: created,
+            updated_at: created,
+        };
+        store.users.insert(user.id, StoredUser { user: user.clone(), password_hash });
+
+        Ok(user)
+    }
+
+    async fn get_user(&self, id: Uuid) -> Result<User, DataError> {
+        let store = self.read()?;
+
+        store.users.get(&id).map(|stored| stored.user.clone()).ok_or(DataError::NotFound)
+    }
+
+    async fn get_users(&self, user_filter: UserFilter) -> Result<Vec<User>, DataError> {
+        let store = self.read()?;
+
+        let mut users: Vec<User> = store.users.values()
+            .map(|stored| &stored.user)
+            .filter(|user| user_filter.username.as_deref().is_none_or(|prefix| user.username.to_string().starts_with(prefix)))
+            .filter(|user| user_filter.email.as_deref().is_none_or(|prefix| user.email.as_str().starts_with(prefix)))
+            .cloned()
+            .collect();
+        users.sort_by_key(|user| user.username.to_string());
+
+        Ok(users)
+    }
+
+    async fn update_user(&self, id: Uuid, update_user: UpdateUser) -> Result<User, DataError> {
+        if update_user.name.is_none() && update_user.email.is_none() {
+            return self.get_user(id).await;
+        }
+
+        let mut store = self.write()?;
+
+        store.users.get(&id).ok_or(DataError::NotFound)?;
+        store.check_unique_user(Some(id), update_user.name.as_ref(), update_user.email.as_ref().map(|e| e.as_str()))?;
+
+        let user = &mut store.users.get_mut(&id).ok_or(DataError::NotFound)?.user;
+        if let Some(name) = update_user.name {
+            user.username = name;
+        }
+        if let Some(email) = update_user.email {
+            user.email = email;
+        }
+        user.updated_at = now();
+
+        Ok(user.clone())
+    }
+
+    async fn delete_user(&self, id: Uuid) -> Result<(), DataError> {
+        let mut store = self.write()?;
+
+        if !store.users.contains_key(&id) {
+            return Err(DataError::NotFound);
+        }
+        if store.posts.values().any(|post| post.author == id) {
+            return Err(DataError::UserHasPosts);
+        }
+
+        store.users.remove(&id);
+        store.sessions.retain(|_, session| session.user_id != id);
+
+        store.comments.retain(|comment| comment.author != id);
+        store.remove_orphaned_comments();
+
+        store.revisions.values_mut().flatten()
+            .filter(|revision| revision.editor == Some(id))
+            .for_each(|revision| revision.editor = None);
+
+        Ok(())
+    }
+
+    async fn get_user_credentials(&self, username: &Username) -> Result<UserCredentials, DataError> {
+        let store = self.read()?;
+
+        store.users.values()
+            .find(|stored| stored.user.username == *username)
+            .map(|stored| UserCredentials {
+                user: stored.user.clone(),
+                password_hash: Some(stored.password_hash.clone()),
+            })
+            .ok_or(DataError::NotFound)
+    }
+
+    async fn update_user_role(&self, id: Uuid, role: Role) -> Result<User, DataError> {
+        let mut store = self.write()?;
+
+        let user = &mut store.users.get_mut(&id).ok_or(DataError::NotFound)?.user;
+        user.role = role;
+        user.updated_at = now();
+
+        Ok(user.clone())
+    }
+}
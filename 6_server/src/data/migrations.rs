@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+use diesel::backend::Backend;
+use diesel::migration::MigrationSource;
+use diesel_migrations::MigrationHarness;
+
+/// What `blog_server migrate <command>` does, `Check` is what startup runs when
+/// `RUN_MIGRATIONS` is off.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MigrationCommand {
+    Up,
+    Down,
+    Status,
+    Check,
+}
+
+impl FromStr for MigrationCommand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "up" => Ok(MigrationCommand::Up),
+            "down" => Ok(MigrationCommand::Down),
+            "status" => Ok(MigrationCommand::Status),
+            _ => Err(anyhow::anyhow!("unknown migrate command: {}, expected up, down or status", s)),
+        }
+    }
+}
+
+/// `(version, name)` of a migration embedded in the binary.
+type KnownMigration = (String, String);
+
+/// Rust code run right before the migration with the given version is applied, for data
+/// changes SQL cannot express, e.g. rendering Markdown. It has to be safe to run twice.
+pub(super) type Backfill<C> = (&'static str, fn(&mut C) -> Result<(), anyhow::Error>);
+
+/// A backend whose schema is managed by the migrations embedded in the binary.
+pub trait Migrate {
+    /// Runs `command` while holding a lock that keeps other replicas from migrating at the
+    /// same time. Returns one human readable line per migration that was touched or listed.
+    fn migrate(&self, command: MigrationCommand) -> Result<Vec<String>, anyhow::Error>;
+}
+
+pub(super) fn execute<DB, C, S>(
+    conn: &mut C,
+    source: S,
+    backfills: &[Backfill<C>],
+    command: MigrationCommand,
+) -> Result<Vec<String>, anyhow::Error>
+where
+    DB: Backend,
+    C: MigrationHarness<DB>,
+    S: MigrationSource<DB>,
+{
+    let (known, applied) = load(conn, &source)?;
+
+    if command == MigrationCommand::Status {
+        return Ok(status(&known, &applied));
+    }
+    ensure_not_ahead(&known, &applied)?;
+
+    match command {
+        MigrationCommand::Up => {
+            let mut lines = Vec::new();
+            for migration in conn.pending_migrations(source).map_err(|e| anyhow::anyhow!(e))? {
+                let version = migration.name().version().to_string();
+                for (_, backfill) in backfills.iter().filter(|(before, _)| *before == version) {
+                    backfill(conn)?;
+                }
+                conn.run_migration(&*migration).map_err(|e| anyhow::anyhow!(e))?;
+                lines.push(format!("Applied {}", version));
+            }
+            Ok(lines)
+        }
+        MigrationCommand::Down => {
+            let version = conn.revert_last_migration(source).map_err(|e| anyhow::anyhow!(e))?;
+            Ok(vec![format!("Reverted {}", version)])
+        }
+        MigrationCommand::Check | MigrationCommand::Status => Ok(pending(&known, &applied)
+            .map(|name| format!("Pending {}", name))
+            .collect()),
+    }
+}
+
+/// Names of the embedded migrations not applied yet, without taking the migration lock.
+/// Fails like startup does when the schema is ahead of this binary.
+pub(super) fn pending_migrations<DB, C, S>(conn: &mut C, source: S) -> Result<Vec<String>, anyhow::Error>
+where
+    DB: Backend,
+    C: MigrationHarness<DB>,
+    S: MigrationSource<DB>,
+{
+    let (known, applied) = load(conn, &source)?;
+    ensure_not_ahead(&known, &applied)?;
+    Ok(pending(&known, &applied).cloned().collect())
+}
+
+fn load<DB, C, S>(conn: &mut C, source: &S) -> Result<(Vec<KnownMigration>, Vec<String>), anyhow::Error>
+where
+    DB: Backend,
+    C: MigrationHarness<DB>,
+    S: MigrationSource<DB>,
+{
+    let known = source.migrations().map_err(|e| anyhow::anyhow!(e))?
+        .iter()
+        .map(|migration| (migration.name().version().to_string(), migration.name().to_string()))
+        .collect();
+    let applied = conn.applied_migrations().map_err(|e| anyhow::anyhow!(e))?
+        .iter()
+        .map(|version| version.to_string())
+        .collect();
+    Ok((known, applied))
+}
+
+fn pending<'a>(known: &'a [KnownMigration], applied: &'a [String]) -> impl Iterator<Item = &'a String> {
+    known.iter()
+        .filter(|(version, _)| !applied.contains(version))
+        .map(|(_, name)| name)
+}
+
+/// `applied` holds the versions recorded in the database.
+fn status(known: &[KnownMigration], applied: &[String]) -> Vec<String> {
+    let mut lines = known.iter()
+        .map(|(version, name)| {
+            let mark = if applied.contains(version) { "x" } else { " " };
+            format!("[{}] {}", mark, name)
+        })
+        .collect::<Vec<_>>();
+    lines.extend(unknown(known, applied).map(|version| format!("[!] {} (not known to this binary)", version)));
+    lines
+}
+
+fn unknown<'a>(known: &'a [KnownMigration], applied: &'a [String]) -> impl Iterator<Item = &'a String> {
+    applied.iter().filter(|applied| !known.iter().any(|(version, _)| version == *applied))
+}
+
+/// Refuses to touch a database migrated by a newer release, running against it would
+/// silently use a schema this binary was never built for.
+fn ensure_not_ahead(known: &[KnownMigration], applied: &[String]) -> Result<(), anyhow::Error> {
+    let ahead = unknown(known, applied).cloned().collect::<Vec<_>>();
+    if ahead.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "database schema is ahead of this binary, unknown migrations applied: {}. Deploy a newer release or revert them with the release that added them",
+        ahead.join(", ")
+    ))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn known() -> Vec<(String, String)> {
+        vec![
+            ("20240101000000".to_string(), "2024-01-01-000000_create_users".to_string()),
+            ("20240201000000".to_string(), "2024-02-01-000000_create_posts".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_schema_ahead_is_rejected() {
+        let known = known();
+
+        assert!(ensure_not_ahead(&known, &strings(&["20240101000000"])).is_ok());
+        assert!(ensure_not_ahead(&known, &strings(&["20240201000000", "20240101000000"])).is_ok());
+        let error = ensure_not_ahead(&known, &strings(&["20240301000000", "20240101000000"])).unwrap_err();
+        assert!(error.to_string().contains("20240301000000"));
+    }
+
+    #[test]
+    fn test_status_lists_applied_pending_and_unknown() {
+        let known = known();
+
+        assert_eq!(status(&known, &strings(&["20240301000000", "20240101000000"])), vec![
+            "[x] 2024-01-01-000000_create_users",
+            "[ ] 2024-02-01-000000_create_posts",
+            "[!] 20240301000000 (not known to this binary)",
+        ]);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod repositories;
 pub mod db;
+pub mod memory;
+pub mod sqlite;
 pub mod data_errors;
 pub mod repo_trait;
+pub mod migrations;
+mod filters;
+pub(crate) mod blocking;
@@ -1,4 +1,10 @@
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::data::repositories::health_repository::HealthRepository;
 use crate::data::repositories::post_repository::PostRepository;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::data::repositories::tag_repository::TagRepository;
 use crate::data::repositories::user_repository::UserRepository;
 
-pub trait DataRepository: UserRepository + PostRepository + Clone {}
+pub trait DataRepository: UserRepository + PostRepository + SessionRepository + SearchRepository + CommentRepository + TagRepository + RevisionRepository + HealthRepository + Clone {}
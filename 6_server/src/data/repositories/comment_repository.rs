@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::comment::{Comment, CreateComment, ModerationStatus};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CommentRepository: Send + Sync + 'static {
+    async fn create_comment(&self, post_id: uuid::Uuid, author: uuid::Uuid, create_comment: CreateComment) -> Result<Comment, DataError>;
+    async fn get_comment(&self, id: uuid::Uuid) -> Result<Comment, DataError>;
+    /// Comments of a post in creation order, optionally restricted to a single moderation status.
+    async fn get_comments(&self, post_id: uuid::Uuid, status: Option<ModerationStatus>) -> Result<Vec<Comment>, DataError>;
+    async fn update_comment_status(&self, id: uuid::Uuid, status: ModerationStatus) -> Result<Comment, DataError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::health::PoolStatus;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HealthRepository: Send + Sync + 'static {
+    /// Runs the cheapest possible query to prove the store answers.
+    async fn ping(&self) -> Result<(), DataError>;
+    /// Names of migrations this binary knows but the database lacks.
+    async fn pending_migrations(&self) -> Result<Vec<String>, DataError>;
+    /// `None` for backends without a connection pool.
+    fn pool_status(&self) -> Option<PoolStatus>;
+}
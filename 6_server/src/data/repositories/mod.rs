@@ -1,2 +1,8 @@
 pub mod post_repository;
 pub mod user_repository;
+pub mod session_repository;
+pub mod search_repository;
+pub mod comment_repository;
+pub mod tag_repository;
+pub mod revision_repository;
+pub mod health_repository;
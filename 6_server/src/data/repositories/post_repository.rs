@@ -1,9 +1,25 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
 use crate::data::data_errors::DataError;
-use crate::models::post::{CreatePost, Post, PostFilter, UpdatePost};
+use crate::models::page::Page;
+use crate::models::post::{CreatePost, Post, PostFilter, PostStatus, UpdatePost};
 
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
 pub trait PostRepository: Send + Sync + 'static {
-    fn create_post(&self, create_post: CreatePost) -> Result<Post, DataError>;
-    fn get_post(&self, id: uuid::Uuid) -> Result<Post, DataError>;
-    fn get_posts(&self, post_filter: PostFilter) -> Result<Vec<Post>, DataError>;
-    fn update_post(&self, id: uuid::Uuid, update_post: UpdatePost) -> Result<Post, DataError>;
+    async fn create_post(&self, author: uuid::Uuid, create_post: CreatePost) -> Result<Post, DataError>;
+    async fn get_post(&self, id: uuid::Uuid) -> Result<Post, DataError>;
+    /// Resolves both current and previous slugs of a post.
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Post, DataError>;
+    async fn get_posts(&self, post_filter: PostFilter) -> Result<Page<Post>, DataError>;
+    async fn update_post(&self, id: uuid::Uuid, editor: uuid::Uuid, update_post: UpdatePost) -> Result<Post, DataError>;
+    async fn delete_post(&self, id: uuid::Uuid) -> Result<(), DataError>;
+    async fn get_deleted_posts(&self) -> Result<Vec<Post>, DataError>;
+    async fn restore_post(&self, id: uuid::Uuid) -> Result<Post, DataError>;
+    /// Moves a post to `status`, failing with `DataError::InvalidTransition` when its current
+    /// status does not allow it. `publish_at` is only kept for `PostStatus::Scheduled`.
+    async fn transition_post(&self, id: uuid::Uuid, status: PostStatus, publish_at: Option<DateTime<Utc>>) -> Result<Post, DataError>;
+    async fn publish_scheduled_posts(&self, now: DateTime<Utc>) -> Result<usize, DataError>;
+    async fn purge_deleted_posts(&self, deleted_before: DateTime<Utc>) -> Result<usize, DataError>;
 }
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::post::Post;
+use crate::models::revision::PostRevision;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RevisionRepository: Send + Sync + 'static {
+    /// Revisions of a post, newest first.
+    async fn get_revisions(&self, post_id: uuid::Uuid) -> Result<Vec<PostRevision>, DataError>;
+    async fn get_revision(&self, post_id: uuid::Uuid, revision: i32) -> Result<PostRevision, DataError>;
+    /// Restores title and body of `revision`, recorded as a new revision by `editor`.
+    async fn rollback_post(&self, post_id: uuid::Uuid, revision: i32, editor: uuid::Uuid) -> Result<Post, DataError>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::search::{SearchQuery, SearchResult};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SearchRepository: Send + Sync + 'static {
+    async fn search_posts(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DataError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::auth::{CreateSession, Session};
+use crate::models::user::User;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SessionRepository: Send + Sync + 'static {
+    async fn create_session(&self, create_session: CreateSession) -> Result<Session, DataError>;
+    async fn get_session_user(&self, token_hash: &str) -> Result<User, DataError>;
+    async fn delete_session(&self, token_hash: &str) -> Result<(), DataError>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::data::data_errors::DataError;
+use crate::models::tag::TagCount;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TagRepository: Send + Sync + 'static {
+    async fn get_tags(&self) -> Result<Vec<TagCount>, DataError>;
+}
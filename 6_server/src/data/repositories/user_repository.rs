@@ -1,7 +1,19 @@
+use async_trait::async_trait;
+
 use crate::data::data_errors::DataError;
-use crate::models::user::{CreateUser, User};
+use crate::models::auth::UserCredentials;
+use crate::models::user::{CreateUser, Role, UpdateUser, User, UserFilter, Username};
 
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
 pub trait UserRepository: Send + Sync + 'static {
-    fn create_user(&self, create_user: CreateUser) -> Result<User, DataError>;
-    fn get_user(&self, id: uuid::Uuid) -> Result<User, DataError>;
+    async fn create_user(&self, create_user: CreateUser) -> Result<User, DataError>;
+    async fn get_user(&self, id: uuid::Uuid) -> Result<User, DataError>;
+    async fn get_users(&self, user_filter: UserFilter) -> Result<Vec<User>, DataError>;
+    async fn update_user(&self, id: uuid::Uuid, update_user: UpdateUser) -> Result<User, DataError>;
+    /// Fails with `UserHasPosts` while the user still owns posts, deleting them along with the
+    /// user would skip the soft delete.
+    async fn delete_user(&self, id: uuid::Uuid) -> Result<(), DataError>;
+    async fn get_user_credentials(&self, username: &Username) -> Result<UserCredentials, DataError>;
+    async fn update_user_role(&self, id: uuid::Uuid, role: Role) -> Result<User, DataError>;
 }
@@ -0,0 +1,118 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::data::sqlite::schema::comments;
+use crate::data::sqlite::schema::comments::dsl::*;
+use crate::data::sqlite::database::{now, parse_id, Sqlite};
+use crate::models::comment::{Comment, CommentBody, CreateComment, ModerationStatus};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = comments)]
+struct DbComment {
+    id: String,
+    post_id: String,
+    author_id: String,
+    parent_id: Option<String>,
+    body: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbComment> for Comment {
+    type Error = Error;
+
+    fn try_from(value: DbComment) -> Result<Self, Self::Error> {
+        Ok(Comment {
+            id: parse_id(&value.id)?,
+            post_id: parse_id(&value.post_id)?,
+            author: parse_id(&value.author_id)?,
+            parent_id: value.parent_id.as_deref().map(parse_id).transpose()?,
+            body: CommentBody::try_new(value.body)?,
+            status: value.status.parse()?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = comments)]
+struct DbCreateComment {
+    id: String,
+    post_id: String,
+    author_id: String,
+    parent_id: Option<String>,
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl CommentRepository for Sqlite {
+    async fn create_comment(&self, post: Uuid, author: Uuid, create_comment: CreateComment) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            conn.immediate_transaction::<_, DataError, _>(|conn| {
+                if let Some(parent) = create_comment.parent_id {
+                    comments.find(parent.to_string()).filter(post_id.eq(post.to_string()))
+                        .select(id)
+                        .get_result::<String>(conn)?;
+                }
+
+                let created = now();
+                Ok(diesel::insert_into(comments)
+                    .values(DbCreateComment {
+                        id: Uuid::new_v4().to_string(),
+                        post_id: post.to_string(),
+                        author_id: author.to_string(),
+                        parent_id: create_comment.parent_id.map(|parent| parent.to_string()),
+                        body: create_comment.body.to_string(),
+                        created_at: created,
+                        updated_at: created,
+                    })
+                    .returning(DbComment::as_returning())
+                    .get_result::<DbComment>(conn)?.try_into()?)
+            })
+        }).await
+    }
+
+    async fn get_comment(&self, comment_id: Uuid) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            Ok(comments.find(comment_id.to_string())
+                .select(DbComment::as_select())
+                .get_result::<DbComment>(conn)?.try_into()?)
+        }).await
+    }
+
+    async fn get_comments(&self, post: Uuid, with_status: Option<ModerationStatus>) -> Result<Vec<Comment>, DataError> {
+        self.run(move |conn| {
+            let mut query = comments
+                .filter(post_id.eq(post.to_string()))
+                .select(DbComment::as_select())
+                .order((created_at.asc(), id.asc()))
+                .into_boxed();
+
+            if let Some(s) = with_status {
+                query = query.filter(status.eq(s.as_str()));
+            }
+
+            query.get_results(conn)?
+                .into_iter()
+                .map(DbComment::try_into).collect::<Result<Vec<Comment>, Error>>().map_err(|e| e.into())
+        }).await
+    }
+
+    async fn update_comment_status(&self, comment_id: Uuid, new_status: ModerationStatus) -> Result<Comment, DataError> {
+        self.run(move |conn| {
+            Ok(diesel::update(comments.find(comment_id.to_string()))
+                .set((status.eq(new_status.as_str()), updated_at.eq(now())))
+                .returning(DbComment::as_returning())
+                .get_result::<DbComment>(conn)?.try_into()?)
+        }).await
+    }
+}
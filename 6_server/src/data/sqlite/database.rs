@@ -0,0 +1,255 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use diesel::connection::SimpleConnection;
+use diesel::r2d2::{ConnectionManager, CustomizeConnection};
+use diesel::SqliteConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use r2d2::Pool;
+use uuid::Uuid;
+
+use crate::config::DbConfig;
+use crate::data::blocking::with_connection;
+use crate::data::data_errors::DataError;
+use crate::data::migrations::{execute, Migrate, MigrationCommand};
+use crate::data::repo_trait::DataRepository;
+use crate::metrics::PoolMetrics;
+
+pub(super) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations_sqlite");
+
+#[derive(Clone)]
+pub struct Sqlite {
+    pub pool: Pool<ConnectionManager<SqliteConnection>>,
+}
+
+/// SQLite keeps foreign keys off unless enabled per connection, and fails right away
+/// instead of waiting while another connection writes.
+#[derive(Debug)]
+struct ConnectionOptions;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ConnectionOptions {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.batch_execute("PRAGMA foreign_keys = ON; PRAGMA busy_timeout = 5000;")
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+impl Sqlite {
+    /// Opens the database file named by a `sqlite://path/to/blog.db` URL.
+    pub fn new(conf: &DbConfig) -> Result<Self, anyhow::Error> {
+        let url = conf.url()?;
+        let path = url.strip_prefix("sqlite://")
+            .or_else(|| url.strip_prefix("sqlite:"))
+            .ok_or_else(|| anyhow::anyhow!("not a sqlite URL"))?;
+
+        let pool = Pool::builder()
+            .connection_customizer(Box::new(ConnectionOptions))
+            .event_handler(Box::new(PoolMetrics))
+            .build(ConnectionManager::<SqliteConnection>::new(path))?;
+        pool.get()?.batch_execute("PRAGMA journal_mode = WAL;")?;
+
+        Ok(Sqlite { pool })
+    }
+
+    pub(super) async fn run<T, F>(&self, f: F) -> Result<T, DataError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SqliteConnection) -> Result<T, DataError> + Send + 'static,
+    {
+        with_connection(&self.pool, f).await
+    }
+}
+
+impl DataRepository for Sqlite {}
+
+impl Migrate for Sqlite {
+    /// The write lock of an immediate transaction serializes concurrent migrations, each
+    /// migration then runs in a savepoint.
+    fn migrate(&self, command: MigrationCommand) -> Result<Vec<String>, anyhow::Error> {
+        self.pool.get()?.immediate_transaction(|conn| execute(conn, MIGRATIONS, &[], command))
+    }
+}
+
+pub(super) fn parse_id(value: &str) -> Result<Uuid, anyhow::Error> {
+    Ok(Uuid::parse_str(value)?)
+}
+
+/// Current time at the microsecond precision Postgres stores, which keeps the text
+/// representation sortable and cursors interchangeable between the backends.
+pub(super) fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(6)
+}
+
+diesel::define_sql_function! {
+    /// 1-based position of `needle` in `haystack`, 0 when absent. Unlike `LIKE`, case-sensitive.
+    fn instr(haystack: diesel::sql_types::Text, needle: diesel::sql_types::Text) -> diesel::sql_types::Integer;
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use chrono::Duration;
+    use diesel::prelude::*;
+    use email_address::EmailAddress;
+
+    use crate::data::data_errors::DataError;
+    use crate::data::repositories::post_repository::PostRepository;
+    use crate::data::repositories::revision_repository::RevisionRepository;
+    use crate::data::repositories::search_repository::SearchRepository;
+    use crate::data::repositories::user_repository::UserRepository;
+    use crate::data::sqlite::schema::posts;
+    use crate::models::auth::Password;
+    use crate::models::post::{CreatePost, Post, PostCursor, PostFilter, PostSort, PostStatus, SortOrder, Title, UpdatePost};
+    use crate::models::search::SearchQuery;
+    use crate::models::tag::TagName;
+    use crate::models::user::{CreateUser, User, Username};
+
+    use super::*;
+
+    /// A single connection keeps the `:memory:` database alive for the whole test.
+    fn setup() -> Sqlite {
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(Box::new(ConnectionOptions))
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        let repo = Sqlite { pool };
+        repo.migrate(MigrationCommand::Up).unwrap();
+        repo
+    }
+
+    async fn create_user(repo: &Sqlite, name: &str, email: &str) -> Result<User, DataError> {
+        repo.create_user(CreateUser {
+            name: Username::try_new(name).unwrap(),
+            email: EmailAddress::from_str(email).unwrap(),
+            password: Password::try_new("supersecret").unwrap(),
+        }).await
+    }
+
+    async fn create_post(repo: &Sqlite, author: Uuid, title: &str, tags: &[&str]) -> Post {
+        repo.create_post(author, CreatePost {
+            title: Title::try_new(title).unwrap(),
+            body: "body".to_string(),
+            tags: tags.iter().map(|name| TagName::try_new(*name).unwrap()).collect(),
+        }).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_user() {
+        let repo = setup();
+        create_user(&repo, "alice", "alice@test.com").await.unwrap();
+
+        assert!(matches!(create_user(&repo, "alice", "other@test.com").await, Err(DataError::Duplicate)));
+        assert!(matches!(create_user(&repo, "other", "alice@test.com").await, Err(DataError::Duplicate)));
+        assert!(matches!(repo.get_user(Uuid::nil()).await, Err(DataError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_filters_and_paginates() {
+        let repo = setup();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        for title in ["Rust basics", "Rust traits", "Go basics"] {
+            create_post(&repo, author, title, &["rust"]).await;
+        }
+        create_post(&repo, author, "Untagged", &[]).await;
+
+        let filter = PostFilter {
+            title: Some("rust".to_string()),
+            tags: Some("rust".to_string()),
+            limit: Some(1),
+            sort: PostSort::Title,
+            order: SortOrder::Asc,
+            ..PostFilter::default()
+        };
+        let first = repo.get_posts(filter.clone()).await.unwrap();
+        assert_eq!(first.items[0].title.as_ref(), "Rust basics");
+        assert_eq!(first.items[0].tags, vec![TagName::try_new("rust").unwrap()]);
+
+        let second = repo.get_posts(PostFilter { cursor: first.next_cursor, ..filter.clone() }).await.unwrap();
+        assert_eq!(second.items[0].title.as_ref(), "Rust traits");
+        assert_eq!(second.next_cursor, None);
+
+        let newest = repo.get_posts(PostFilter { limit: Some(2), ..PostFilter::default() }).await.unwrap();
+        let oldest = repo.get_posts(PostFilter { cursor: newest.next_cursor, ..PostFilter::default() }).await.unwrap();
+        assert_eq!(oldest.items.iter().map(|post| post.title.as_ref()).collect::<Vec<_>>(), vec!["Rust traits", "Rust basics"]);
+
+        let wrong_sort = PostFilter { cursor: Some(PostCursor::after(&second.items[0], PostSort::CreatedAt).encode()), ..filter };
+        assert!(matches!(repo.get_posts(wrong_sort).await, Err(DataError::InvalidCursor)));
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_created_between() {
+        let repo = setup();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let bound = DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        for (title, offset) in [("Just before", -1), ("Exactly at", 0), ("Just after", 1)] {
+            let post = create_post(&repo, author, title, &[]).await;
+            repo.run(move |conn| {
+                diesel::update(posts::table.find(post.id.to_string()))
+                    .set(posts::created_at.eq(bound + Duration::seconds(offset)))
+                    .execute(conn)?;
+                Ok(())
+            }).await.unwrap();
+        }
+
+        let titles = |after: Option<DateTime<Utc>>, before: Option<DateTime<Utc>>| {
+            let filter = PostFilter { created_after: after, created_before: before, order: SortOrder::Asc, ..PostFilter::default() };
+            let repo = repo.clone();
+            async move {
+                repo.get_posts(filter).await.unwrap().items.into_iter().map(|post| post.title.into_inner()).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(titles(Some(bound), None).await, vec!["Exactly at", "Just after"]);
+        assert_eq!(titles(None, Some(bound)).await, vec!["Just before"]);
+        assert_eq!(titles(Some(bound - Duration::seconds(1)), Some(bound + Duration::seconds(1))).await, vec!["Just before", "Exactly at"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_post_keeps_slug_history_and_revisions() {
+        let repo = setup();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let post = create_post(&repo, author, "First title", &[]).await;
+
+        let updated = repo.update_post(post.id, author, UpdatePost {
+            title: Some(Title::try_new("Second title").unwrap()),
+            body: None,
+            tags: None,
+        }).await.unwrap();
+
+        assert_eq!(updated.slug, "second-title");
+        assert_eq!(repo.get_post_by_slug("first-title").await.unwrap().id, post.id);
+        assert_eq!(create_post(&repo, author, "First title", &[]).await.slug, "first-title-2");
+        assert_eq!(repo.get_revisions(post.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_keeps_posts() {
+        let repo = setup();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        let post = create_post(&repo, author, "Doomed", &[]).await;
+
+        assert!(matches!(repo.delete_user(author).await, Err(DataError::UserHasPosts)));
+        repo.delete_post(post.id).await.unwrap();
+        assert!(matches!(repo.delete_user(author).await, Err(DataError::UserHasPosts)));
+
+        repo.purge_deleted_posts(Utc::now() + Duration::seconds(1)).await.unwrap();
+        repo.delete_user(author).await.unwrap();
+
+        assert!(matches!(repo.get_user(author).await, Err(DataError::NotFound)));
+        assert!(repo.get_revisions(post.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_skips_unpublished_posts() {
+        let repo = setup();
+        let author = create_user(&repo, "alice", "alice@test.com").await.unwrap().id;
+        create_post(&repo, author, "Rust draft", &[]).await;
+        let published = create_post(&repo, author, "Rust published", &[]).await;
+        repo.transition_post(published.id, PostStatus::InReview, None).await.unwrap();
+        repo.transition_post(published.id, PostStatus::Published, None).await.unwrap();
+
+        let hits = repo.search_posts(SearchQuery { q: "rust".to_string(), limit: None }).await.unwrap();
+
+        assert_eq!(hits.iter().map(|hit| hit.post.id).collect::<Vec<_>>(), vec![published.id]);
+    }
+}
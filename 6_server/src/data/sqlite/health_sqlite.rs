@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use diesel::RunQueryDsl;
+
+use crate::data::blocking::pool_status;
+use crate::data::data_errors::DataError;
+use crate::data::migrations::pending_migrations;
+use crate::data::repositories::health_repository::HealthRepository;
+use crate::data::sqlite::database::{Sqlite, MIGRATIONS};
+use crate::models::health::PoolStatus;
+
+#[async_trait]
+impl HealthRepository for Sqlite {
+    async fn ping(&self) -> Result<(), DataError> {
+        self.run(move |conn| {
+            diesel::sql_query("SELECT 1").execute(conn)?;
+            Ok(())
+        }).await
+    }
+
+    async fn pending_migrations(&self) -> Result<Vec<String>, DataError> {
+        self.run(move |conn| Ok(pending_migrations(conn, MIGRATIONS)?)).await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        Some(pool_status(&self.pool))
+    }
+}
@@ -0,0 +1,10 @@
+pub mod database;
+mod users_sqlite;
+mod posts_sqlite;
+mod sessions_sqlite;
+mod search_sqlite;
+mod comments_sqlite;
+mod tags_sqlite;
+mod revisions_sqlite;
+mod health_sqlite;
+mod schema;
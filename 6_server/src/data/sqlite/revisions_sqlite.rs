@@ -0,0 +1,102 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::dsl::max;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::data::sqlite::posts_sqlite::apply_update;
+use crate::data::sqlite::schema::post_revisions;
+use crate::data::sqlite::schema::post_revisions::dsl::*;
+use crate::data::sqlite::database::{now, parse_id, Sqlite};
+use crate::models::post::{Post, Title, UpdatePost};
+use crate::models::revision::PostRevision;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = post_revisions)]
+struct DbPostRevision {
+    post_id: String,
+    revision: i32,
+    title: String,
+    body: String,
+    editor_id: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbPostRevision> for PostRevision {
+    type Error = Error;
+
+    fn try_from(value: DbPostRevision) -> Result<Self, Self::Error> {
+        Ok(PostRevision {
+            post_id: parse_id(&value.post_id)?,
+            revision: value.revision,
+            title: Title::try_new(value.title)?,
+            body: value.body,
+            editor: value.editor_id.as_deref().map(parse_id).transpose()?,
+            created_at: value.created_at,
+        })
+    }
+}
+
+/// Appends the next revision of a post. Callers run inside an immediate transaction, which
+/// keeps revision numbers gapless and unique.
+pub(super) fn record_revision(conn: &mut SqliteConnection, post: Uuid, editor: Option<Uuid>, new_title: &str, new_body: &str) -> Result<(), DataError> {
+    let latest = post_revisions
+        .filter(post_id.eq(post.to_string()))
+        .select(max(revision))
+        .get_result::<Option<i32>>(conn)?;
+
+    diesel::insert_into(post_revisions)
+        .values((
+            post_id.eq(post.to_string()),
+            revision.eq(latest.unwrap_or(0) + 1),
+            title.eq(new_title),
+            body.eq(new_body),
+            editor_id.eq(editor.map(|e| e.to_string())),
+            created_at.eq(now()),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl RevisionRepository for Sqlite {
+    async fn get_revisions(&self, post: Uuid) -> Result<Vec<PostRevision>, DataError> {
+        self.run(move |conn| {
+            post_revisions
+                .filter(post_id.eq(post.to_string()))
+                .order(revision.desc())
+                .select(DbPostRevision::as_select())
+                .get_results(conn)?
+                .into_iter()
+                .map(DbPostRevision::try_into).collect::<Result<Vec<PostRevision>, Error>>().map_err(|e| e.into())
+        }).await
+    }
+
+    async fn get_revision(&self, post: Uuid, number: i32) -> Result<PostRevision, DataError> {
+        self.run(move |conn| {
+            Ok(post_revisions.find((post.to_string(), number))
+                .select(DbPostRevision::as_select())
+                .get_result::<DbPostRevision>(conn)?.try_into()?)
+        }).await
+    }
+
+    async fn rollback_post(&self, post: Uuid, number: i32, editor: Uuid) -> Result<Post, DataError> {
+        self.run(move |conn| {
+            conn.immediate_transaction::<_, DataError, _>(|conn| {
+                let target: PostRevision = post_revisions.find((post.to_string(), number))
+                    .select(DbPostRevision::as_select())
+                    .get_result::<DbPostRevision>(conn)?.try_into()?;
+
+                apply_update(conn, post, editor, UpdatePost {
+                    title: Some(target.title),
+                    body: Some(target.body),
+                    tags: None,
+                })
+            })
+        }).await
+    }
+}
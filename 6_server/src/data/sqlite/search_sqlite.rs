@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::data::data_errors::DataError;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::data::sqlite::posts_sqlite::{into_posts, DbPost};
+use crate::data::sqlite::schema::posts;
+use crate::data::sqlite::database::Sqlite;
+use crate::models::post::{Post, PostStatus};
+use crate::models::search::{SearchQuery, SearchResult, SearchTerms};
+
+#[async_trait]
+impl SearchRepository for Sqlite {
+    /// SQLite has no equivalent of the Postgres text search configuration, so posts are
+    /// matched in memory. Good enough for the small deployments this backend is meant for.
+    async fn search_posts(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DataError> {
+        self.run(move |conn| {
+            let terms = SearchTerms::parse(&query.q);
+
+            let rows = posts::table
+                .filter(posts::deleted_at.is_null())
+                .filter(posts::status.eq(PostStatus::Published.as_str()))
+                .select(DbPost::as_select())
+                .load::<DbPost>(conn)?;
+
+            let mut hits: Vec<(Post, f32)> = into_posts(conn, rows)?
+                .into_iter()
+                .filter_map(|post| terms.rank(&post).map(|rank| (post, rank)))
+                .collect();
+            hits.sort_by(|(a, a_rank), (b, b_rank)| b_rank.total_cmp(a_rank)
+                .then(b.created_at.cmp(&a.created_at))
+                .then(a.id.cmp(&b.id)));
+
+            Ok(hits.into_iter()
+                .take(query.page_size() as usize)
+                .map(|(post, rank)| SearchResult {
+                    snippet: terms.snippet(&post.body),
+                    post,
+                    rank,
+                })
+                .collect())
+        }).await
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::data::data_errors::DataError;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::data::sqlite::schema::{sessions, users};
+use crate::data::sqlite::schema::sessions::dsl::*;
+use crate::data::sqlite::database::{now, parse_id, Sqlite};
+use crate::data::sqlite::users_sqlite::DbUser;
+use crate::models::auth::{CreateSession, Session};
+use crate::models::user::User;
+
+#[derive(Insertable)]
+#[diesel(table_name = sessions)]
+struct DbCreateSession {
+    token_hash: String,
+    user_id: String,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<CreateSession> for DbCreateSession {
+    fn from(session: CreateSession) -> Self {
+        DbCreateSession {
+            token_hash: session.token_hash,
+            user_id: session.user_id.to_string(),
+            created_at: now(),
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for Sqlite {
+    async fn create_session(&self, create_session: CreateSession) -> Result<Session, DataError> {
+        self.run(move |conn| {
+            let (uid, expires): (String, DateTime<Utc>) = diesel::insert_into(sessions)
+                .values(DbCreateSession::from(create_session))
+                .returning((user_id, expires_at))
+                .get_result(conn)?;
+
+            Ok(Session { user_id: parse_id(&uid)?, expires_at: expires })
+        }).await
+    }
+
+    async fn get_session_user(&self, search_hash: &str) -> Result<User, DataError> {
+        let search_hash = search_hash.to_string();
+
+        self.run(move |conn| {
+            let user: DbUser = sessions
+                .inner_join(users::table)
+                .filter(token_hash.eq(search_hash))
+                .filter(expires_at.gt(Utc::now()))
+                .select(DbUser::as_select())
+                .get_result(conn)?;
+
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    async fn delete_session(&self, search_hash: &str) -> Result<(), DataError> {
+        let search_hash = search_hash.to_string();
+
+        self.run(move |conn| {
+            diesel::delete(sessions.filter(token_hash.eq(search_hash)))
+                .execute(conn)?;
+
+            Ok(())
+        }).await
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::data::data_errors::DataError;
+use crate::data::repositories::user_repository::UserRepository;
+use crate::data::sqlite::schema::{posts, users};
+use crate::data::sqlite::schema::users::{email, password_hash, role, table, updated_at, username};
+use crate::data::sqlite::database::{instr, now, parse_id, Sqlite};
+use crate::models::auth::UserCredentials;
+use crate::models::user::{CreateUser, Role, UpdateUser, User, UserFilter, Username};
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = users)]
+pub(super) struct DbUser {
+    id: String,
+    username: String,
+    email: String,
+    role: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbUser> for User {
+    type Error = Error;
+
+    fn try_from(value: DbUser) -> Result<Self, Self::Error> {
+        Ok(User {
+            id: parse_id(&value.id)?,
+            username: Username::try_new(value.username)?,
+            email: value.email.parse()?,
+            role: value.role.parse()?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+struct DbCreateUser {
+    id: String,
+    username: String,
+    email: String,
+    password_hash: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<CreateUser> for DbCreateUser {
+    type Error = Error;
+
+    fn try_from(user: CreateUser) -> Result<Self, Self::Error> {
+        let created = now();
+        Ok(DbCreateUser {
+            id: Uuid::new_v4().to_string(),
+            password_hash: user.password.hash()?,
+            username: user.name.to_string(),
+            email: user.email.to_string(),
+            created_at: created,
+            updated_at: created,
+        })
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = users)]
+struct DbUpdateUser {
+    username: Option<String>,
+    email: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<UpdateUser> for DbUpdateUser {
+    fn from(user: UpdateUser) -> Self {
+        DbUpdateUser {
+            username: user.name.map(|n| n.to_string()),
+            email: user.email.map(|e| e.to_string()),
+            updated_at: now(),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for Sqlite {
+    async fn create_user(&self, create_user: CreateUser) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = diesel::insert_into(table)
+                .values(DbCreateUser::try_from(create_user)?)
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    async fn get_user(&self, id: Uuid) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = table.find(id.to_string())
+                .select(DbUser::as_select())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    async fn get_users(&self, user_filter: UserFilter) -> Result<Vec<User>, DataError> {
+        self.run(move |conn| {
+            let mut query = table.select(DbUser::as_select()).into_boxed();
+
+            if let Some(q_username) = user_filter.username {
+                query = query.filter(instr(username, q_username).eq(1));
+            }
+
+            if let Some(q_email) = user_filter.email {
+                query = query.filter(instr(email, q_email).eq(1));
+            }
+
+            query.order(username.asc())
+                .get_results(conn)?
+                .into_iter()
+                .map(DbUser::try_into).collect::<Result<Vec<User>, Error>>().map_err(|e| e.into())
+        }).await
+    }
+
+    async fn update_user(&self, id: Uuid, update_user: UpdateUser) -> Result<User, DataError> {
+        if update_user.name.is_none() && update_user.email.is_none() {
+            return self.get_user(id).await;
+        }
+
+        self.run(move |conn| {
+            let user: DbUser = diesel::update(table.find(id.to_string()))
+                .set(DbUpdateUser::from(update_user))
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+
+    async fn delete_user(&self, id: Uuid) -> Result<(), DataError> {
+        self.run(move |conn| {
+            conn.transaction(|conn| {
+                let owns_posts = diesel::select(diesel::dsl::exists(posts::table.filter(posts::author_id.eq(id.to_string()))))
+                    .get_result::<bool>(conn)?;
+                if owns_posts {
+                    return Err(DataError::UserHasPosts);
+                }
+
+                match diesel::delete(table.find(id.to_string())).execute(conn)? {
+                    0 => Err(DataError::NotFound),
+                    _ => Ok(()),
+                }
+            })
+        }).await
+    }
+
+    async fn get_user_credentials(&self, name: &Username) -> Result<UserCredentials, DataError> {
+        let name = name.to_string();
+
+        self.run(move |conn| {
+            let (user, hash): (DbUser, Option<String>) = table
+                .filter(username.eq(name))
+                .select((DbUser::as_select(), password_hash))
+                .get_result(conn)?;
+            Ok(UserCredentials {
+                user: user.try_into()?,
+                password_hash: hash,
+            })
+        }).await
+    }
+
+    async fn update_user_role(&self, id: Uuid, new_role: Role) -> Result<User, DataError> {
+        self.run(move |conn| {
+            let user: DbUser = diesel::update(table.find(id.to_string()))
+                .set((role.eq(new_role.as_str()), updated_at.eq(now())))
+                .returning(DbUser::as_returning())
+                .get_result(conn)?;
+            Ok(user.try_into()?)
+        }).await
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::config::JobsConfig;
+use crate::data::repositories::post_repository::PostRepository;
+
+pub fn spawn_purge_deleted_posts(repository: Arc<dyn PostRepository>, config: &JobsConfig) -> JoinHandle<()> {
+    let retention = chrono::Duration::days(config.post_retention_days);
+    let mut interval = tokio::time::interval(Duration::from_secs(config.purge_interval_secs));
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            match repository.purge_deleted_posts(Utc::now() - retention).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} deleted posts", purged),
+                Err(e) => error!("Failed to purge deleted posts: {:?}", e),
+            }
+        }
+    })
+}
+
+pub fn spawn_publish_scheduled_posts(repository: Arc<dyn PostRepository>, config: &JobsConfig) -> JoinHandle<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.publish_interval_secs));
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            match repository.publish_scheduled_posts(Utc::now()).await {
+                Ok(0) => {}
+                Ok(published) => info!("Published {} scheduled posts", published),
+                Err(e) => error!("Failed to publish scheduled posts: {:?}", e),
+            }
+        }
+    })
+}
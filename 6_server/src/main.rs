@@ -1,27 +1,88 @@
 use envconfig::Envconfig;
-use tracing::log::info;
+use tracing::log::{info, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 
-use crate::config::Config;
+use crate::config::{Config, DbBackend, DbConfig, LogFormat, Storage};
 use crate::data::db::postgres::Postgres;
+use crate::data::memory::in_memory::InMemory;
+use crate::data::migrations::{Migrate, MigrationCommand};
+use crate::data::repositories::user_repository::UserRepository;
+use crate::data::sqlite::database::Sqlite;
+use crate::models::user::{Role, User, Username};
 use crate::server::app::define_app;
+use crate::server::state::AppState;
+use crate::services::PostRepositoryProvider;
 
 pub mod data;
 pub mod server;
 pub mod models;
 pub mod config;
 pub mod services;
+pub mod jobs;
+pub mod telemetry;
+pub mod metrics;
 
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
     let _ = dotenvy::dotenv();
 
     let config = Config::init_from_env().expect("Failed to load config");
 
-    let postgres = Postgres::new(&config.db).expect("Failed to connect to Postgres");
+    let tracer_provider = telemetry::otlp_tracer_provider(&config.telemetry).expect("Invalid OpenTelemetry config");
+    let fmt_layer = match config.log_format {
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer().json().with_current_span(false).boxed(),
+    };
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(tracer_provider.as_ref().map(telemetry::layer))
+        .with(LevelFilter::INFO)
+        .init();
 
-    let app = define_app(postgres.into());
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["migrate", command] => {
+            let command = command.parse().expect("Usage: blog_server migrate <up|down|status>");
+            for line in open_database(&config.db).migrate(command).expect("Migration failed") {
+                println!("{}", line);
+            }
+            return;
+        }
+        ["set-role", name, role] => {
+            let user = set_role(&config.db, name, role).await.expect("Failed to set role");
+            println!("{} is now {}", user.username, user.role.as_str());
+            return;
+        }
+        _ => {}
+    }
+
+    let state = match config.storage {
+        Storage::Database => match config.db.backend().expect("Invalid DATABASE_URL") {
+            DbBackend::Postgres => {
+                let db = Postgres::new(&config.db).expect("Failed to connect to Postgres");
+                prepare_schema(&db, &config.db);
+                AppState::from(db)
+            }
+            DbBackend::Sqlite => {
+                let db = Sqlite::new(&config.db).expect("Failed to open SQLite database");
+                prepare_schema(&db, &config.db);
+                AppState::from(db)
+            }
+        },
+        Storage::Memory => {
+            info!("Using in-memory storage, data is lost on shutdown");
+            AppState::from(InMemory::new())
+        }
+    }.with_site(config.site.clone());
+
+    jobs::spawn_purge_deleted_posts(state.post_repository(), &config.jobs);
+    jobs::spawn_publish_scheduled_posts(state.post_repository(), &config.jobs);
+
+    let app = define_app(state);
 
     let addr = config.to_socket_addr();
     let listener = tokio::net::TcpListener::bind(addr).await.expect("Failed to bind to address");
@@ -29,4 +90,43 @@ async fn main() {
     info!("Server listening on: {}", addr);
 
     axum::serve(listener, app).await.expect("Failed to start server");
+
+    if let Some(provider) = tracer_provider {
+        let _ = provider.shutdown();
+    }
+}
+
+fn open_database(conf: &DbConfig) -> Box<dyn Migrate> {
+    match conf.backend().expect("Invalid DATABASE_URL") {
+        DbBackend::Postgres => Box::new(Postgres::new(conf).expect("Failed to connect to Postgres")),
+        DbBackend::Sqlite => Box::new(Sqlite::new(conf).expect("Failed to open SQLite database")),
+    }
+}
+
+/// Changes a user's role without going through the API, the only way to get the first admin
+/// of a new deployment since `PUT /api/user/:id/role` is open to admins only.
+async fn set_role(conf: &DbConfig, name: &str, role: &str) -> Result<User, anyhow::Error> {
+    let name = Username::try_new(name)?;
+    let role: Role = role.parse()?;
+    let users: Box<dyn UserRepository> = match conf.backend()? {
+        DbBackend::Postgres => Box::new(Postgres::new(conf)?),
+        DbBackend::Sqlite => Box::new(Sqlite::new(conf)?),
+    };
+
+    let user = users.get_user_credentials(&name).await
+        .map_err(|e| anyhow::anyhow!("user {}: {}", name, e))?
+        .user;
+    Ok(users.update_user_role(user.id, role).await?)
+}
+
+/// Migrates the schema when `RUN_MIGRATIONS` is set, otherwise only refuses to start
+/// against a schema that is ahead of this binary.
+fn prepare_schema(db: &impl Migrate, conf: &DbConfig) {
+    let command = if conf.run_migrations { MigrationCommand::Up } else { MigrationCommand::Check };
+    for line in db.migrate(command).expect("Database schema is not usable") {
+        match command {
+            MigrationCommand::Check => warn!("{}, set RUN_MIGRATIONS=true or run `blog_server migrate up`", line),
+            _ => info!("{}", line),
+        }
+    }
 }
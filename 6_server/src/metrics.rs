@@ -0,0 +1,77 @@
+use std::sync::LazyLock;
+
+use prometheus::{Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+use r2d2::event::CheckoutEvent;
+use r2d2::HandleEvent;
+
+use crate::models::health::PoolStatus;
+
+/// Process wide Prometheus metrics. A global because they are recorded from places without
+/// access to the app state, e.g. `IntoResponse` impls and r2d2 pool events.
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests: IntCounterVec,
+    pub http_request_duration: HistogramVec,
+    pub data_errors: IntCounterVec,
+    pub pool_connections: IntGaugeVec,
+    pub pool_wait: Histogram,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by matched route and status"),
+            &["method", "route", "status"],
+        ).expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency by matched route and status"),
+            &["method", "route", "status"],
+        ).expect("valid metric");
+        let data_errors = IntCounterVec::new(
+            Opts::new("data_errors_total", "Errors answered to clients by DataError variant"),
+            &["code"],
+        ).expect("valid metric");
+        let pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database connections by state, sampled when scraped"),
+            &["state"],
+        ).expect("valid metric");
+        let pool_wait = Histogram::with_opts(
+            HistogramOpts::new("db_pool_wait_seconds", "Time spent waiting to check out a database connection")
+                .buckets(vec![0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0]),
+        ).expect("valid metric");
+
+        let registry = Registry::new();
+        registry.register(Box::new(http_requests.clone())).expect("unique metric");
+        registry.register(Box::new(http_request_duration.clone())).expect("unique metric");
+        registry.register(Box::new(data_errors.clone())).expect("unique metric");
+        registry.register(Box::new(pool_connections.clone())).expect("unique metric");
+        registry.register(Box::new(pool_wait.clone())).expect("unique metric");
+
+        Metrics { registry, http_requests, http_request_duration, data_errors, pool_connections, pool_wait }
+    }
+
+    pub fn set_pool_status(&self, status: PoolStatus) {
+        self.pool_connections.with_label_values(&["in_use"]).set(status.in_use().into());
+        self.pool_connections.with_label_values(&["idle"]).set(status.idle.into());
+        self.pool_connections.with_label_values(&["max"]).set(status.max_size.into());
+    }
+
+    /// Everything registered, in the Prometheus text exposition format.
+    pub fn encode(&self) -> Result<String, anyhow::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+/// Pool event handler feeding `db_pool_wait_seconds`, install with `Pool::builder().event_handler(...)`.
+#[derive(Debug)]
+pub struct PoolMetrics;
+
+impl HandleEvent for PoolMetrics {
+    fn handle_checkout(&self, event: CheckoutEvent) {
+        METRICS.pool_wait.observe(event.duration().as_secs_f64());
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{Debug, Formatter};
+
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::SaltString;
+use chrono::{DateTime, Utc};
+use nutype::nutype;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::models::user::{User, Username};
+
+#[nutype(
+    validate(not_empty, len_char_max = 128, len_char_min = 8),
+    derive(Clone, Deserialize, AsRef)
+)]
+pub struct Password(String);
+
+impl Debug for Password {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Password(***)")
+    }
+}
+
+impl Password {
+    pub fn hash(&self) -> Result<String, anyhow::Error> {
+        let salt = SaltString::generate(&mut OsRng);
+        Ok(Argon2::default()
+            .hash_password(self.as_ref().as_bytes(), &salt)?
+            .to_string())
+    }
+
+    pub fn verify(&self, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash)
+            .and_then(|hash| Argon2::default().verify_password(self.as_ref().as_bytes(), &hash))
+            .is_ok()
+    }
+
+    /// `hash` on tokio's blocking thread pool, argon2 is too slow and memory hungry to run
+    /// on an async worker thread.
+    pub async fn hash_blocking(self) -> Result<String, anyhow::Error> {
+        tokio::task::spawn_blocking(move || self.hash()).await?
+    }
+
+    /// `verify` on tokio's blocking thread pool, see `hash_blocking`.
+    pub async fn verify_blocking(self, password_hash: String) -> Result<bool, anyhow::Error> {
+        Ok(tokio::task::spawn_blocking(move || self.verify(&password_hash)).await?)
+    }
+}
+
+pub struct SessionToken(String);
+
+impl SessionToken {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        SessionToken(hex::encode(bytes))
+    }
+
+    pub fn hash(&self) -> String {
+        hex::encode(Sha256::digest(self.0.as_bytes()))
+    }
+}
+
+impl From<String> for SessionToken {
+    fn from(token: String) -> Self {
+        SessionToken(token)
+    }
+}
+
+impl AsRef<str> for SessionToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserCredentials {
+    pub user: User,
+    pub password_hash: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSession {
+    pub token_hash: String,
+    pub user_id: uuid::Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Session {
+    pub user_id: uuid::Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub username: Username,
+    pub password: Password,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
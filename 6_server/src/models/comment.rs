@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use nutype::nutype;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Comment {
+    pub id: uuid::Uuid,
+    pub post_id: uuid::Uuid,
+    pub author: uuid::Uuid,
+    pub parent_id: Option<uuid::Uuid>,
+    pub body: CommentBody,
+    pub status: ModerationStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[nutype(
+    sanitize(trim),
+    validate(not_empty, len_char_max = 5000),
+    derive(Debug, PartialEq, Clone, Serialize, Deserialize, Display)
+)]
+pub struct CommentBody(String);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ModerationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationStatus::Pending => "pending",
+            ModerationStatus::Approved => "approved",
+            ModerationStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl FromStr for ModerationStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ModerationStatus::Pending),
+            "approved" => Ok(ModerationStatus::Approved),
+            "rejected" => Ok(ModerationStatus::Rejected),
+            _ => Err(anyhow::anyhow!("unknown moderation status: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreateComment {
+    pub body: CommentBody,
+    pub parent_id: Option<uuid::Uuid>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UpdateCommentStatus {
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub replies: Vec<CommentThread>,
+}
+
+/// How many levels replies nest below a top-level comment. Threads are built and serialized
+/// recursively, so without a bound a long enough chain of replies overflows the stack.
+pub const MAX_REPLY_DEPTH: usize = 8;
+
+impl CommentThread {
+    /// Nests `comments` under their parents, keeping the input order within each level.
+    /// Replies whose parent is not part of `comments` (e.g. hidden by moderation) are dropped.
+    /// Replies nested deeper than `MAX_REPLY_DEPTH` are listed flat under their deepest ancestor.
+    pub fn build(comments: Vec<Comment>) -> Vec<CommentThread> {
+        let mut children: HashMap<Option<uuid::Uuid>, Vec<Comment>> = HashMap::new();
+        for comment in comments {
+            children.entry(comment.parent_id).or_default().push(comment);
+        }
+
+        fn attach(parent: Option<uuid::Uuid>, depth: usize, children: &mut HashMap<Option<uuid::Uuid>, Vec<Comment>>) -> Vec<CommentThread> {
+            let comments = children.remove(&parent).unwrap_or_default();
+            if depth > MAX_REPLY_DEPTH {
+                return flatten(comments, children);
+            }
+
+            comments.into_iter()
+                .map(|comment| {
+                    let replies = attach(Some(comment.id), depth + 1, children);
+                    CommentThread { comment, replies }
+                })
+                .collect()
+        }
+
+        /// `comments` and all of their replies in reading order, without nesting.
+        fn flatten(comments: Vec<Comment>, children: &mut HashMap<Option<uuid::Uuid>, Vec<Comment>>) -> Vec<CommentThread> {
+            let mut flat = Vec::new();
+            let mut stack: Vec<Comment> = comments.into_iter().rev().collect();
+            while let Some(comment) = stack.pop() {
+                stack.extend(children.remove(&Some(comment.id)).unwrap_or_default().into_iter().rev());
+                flat.push(CommentThread { comment, replies: Vec::new() });
+            }
+            flat
+        }
+
+        attach(None, 0, &mut children)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gen_comment(id: u8, parent: Option<u8>) -> Comment {
+        Comment {
+            id: uuid::Uuid::from_bytes([id; 16]),
+            post_id: uuid::Uuid::from_bytes([0; 16]),
+            author: uuid::Uuid::from_bytes([0; 16]),
+            parent_id: parent.map(|p| uuid::Uuid::from_bytes([p; 16])),
+            body: CommentBody::try_new("comment").unwrap(),
+            status: ModerationStatus::Approved,
+            created_at: DateTime::from_timestamp(id as i64, 0).unwrap(),
+            updated_at: DateTime::from_timestamp(id as i64, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_build_threads() {
+        let threads = CommentThread::build(vec![
+            gen_comment(1, None),
+            gen_comment(2, Some(1)),
+            gen_comment(3, None),
+            gen_comment(4, Some(2)),
+            gen_comment(5, Some(9)),
+        ]);
+
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].comment.id, uuid::Uuid::from_bytes([1; 16]));
+        assert_eq!(threads[0].replies[0].comment.id, uuid::Uuid::from_bytes([2; 16]));
+        assert_eq!(threads[0].replies[0].replies[0].comment.id, uuid::Uuid::from_bytes([4; 16]));
+        assert!(threads[1].replies.is_empty());
+    }
+
+    #[test]
+    fn test_build_flattens_deep_threads() {
+        let chain = (0..10_000u32)
+            .map(|n| Comment {
+                id: uuid::Uuid::from_u128(n as u128 + 1),
+                parent_id: n.checked_sub(1).map(|parent| uuid::Uuid::from_u128(parent as u128 + 1)),
+                ..gen_comment(1, None)
+            })
+            .collect();
+
+        let threads = CommentThread::build(chain);
+
+        let mut deepest = &threads[0];
+        for _ in 0..MAX_REPLY_DEPTH {
+            deepest = &deepest.replies[0];
+        }
+        assert_eq!(deepest.replies.len(), 10_000 - MAX_REPLY_DEPTH - 1);
+        assert!(deepest.replies.iter().all(|reply| reply.replies.is_empty()));
+        assert_eq!(deepest.replies[0].comment.id, uuid::Uuid::from_u128(MAX_REPLY_DEPTH as u128 + 2));
+        assert!(serde_json::to_string(&threads).is_ok());
+    }
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+/// Usage of a connection pool, `connections` counts idle and checked out connections.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+pub struct PoolStatus {
+    pub max_size: u32,
+    pub connections: u32,
+    pub idle: u32,
+}
+
+impl PoolStatus {
+    pub fn in_use(&self) -> u32 {
+        self.connections - self.idle
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+}
+
+/// Body of `/readyz`, `status` is `Degraded` as soon as the database or migrations are. The
+/// pool is only reported, a saturated pool is load rather than a broken replica and taking
+/// every busy replica out of rotation would push its load onto the rest.
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    pub status: HealthStatus,
+    pub database: DatabaseHealth,
+    pub migrations: MigrationsHealth,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<PoolHealth>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseHealth {
+    pub status: HealthStatus,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationsHealth {
+    pub status: HealthStatus,
+    pub pending: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Degraded once every connection is checked out, new requests then queue for one.
+#[derive(Debug, Serialize)]
+pub struct PoolHealth {
+    pub status: HealthStatus,
+    pub in_use: u32,
+    #[serde(flatten)]
+    pub usage: PoolStatus,
+}
+
+impl Readiness {
+    pub fn new(database: DatabaseHealth, migrations: MigrationsHealth, pool: Option<PoolHealth>) -> Self {
+        let status = match [database.status, migrations.status].contains(&HealthStatus::Degraded) {
+            true => HealthStatus::Degraded,
+            false => HealthStatus::Ok,
+        };
+
+        Readiness { status, database, migrations, pool }
+    }
+}
+
+impl From<PoolStatus> for PoolHealth {
+    fn from(usage: PoolStatus) -> Self {
+        let status = match usage.in_use() >= usage.max_size {
+            true => HealthStatus::Degraded,
+            false => HealthStatus::Ok,
+        };
+
+        PoolHealth { status, in_use: usage.in_use(), usage }
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use syntect::html::{ClassedHTMLGenerator, ClassStyle};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+pub const EXCERPT_LENGTH: usize = 200;
+pub const WORDS_PER_MINUTE: usize = 200;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Everything derived from a Markdown post body, computed once when the post is written.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedBody {
+    pub html: String,
+    pub excerpt: String,
+    pub reading_time_minutes: i32,
+}
+
+/// Renders `markdown` to sanitized HTML. Fenced code blocks with a known language are
+/// highlighted with `syntect` CSS classes (e.g. `<span class="keyword">`), so the
+/// stylesheet decides on the theme.
+pub fn render(markdown: &str) -> RenderedBody {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events: Vec<Event> = Parser::new_ext(markdown, options).collect();
+
+    let text = plain_text(&events);
+    let words = text.split_whitespace().count();
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, highlight_code_blocks(events).into_iter());
+
+    RenderedBody {
+        html: sanitize(&html),
+        excerpt: excerpt(&text, EXCERPT_LENGTH),
+        reading_time_minutes: words.div_ceil(WORDS_PER_MINUTE).max(1) as i32,
+    }
+}
+
+/// Replaces fenced code blocks in a known language with highlighted HTML; all other code
+/// blocks are left to pulldown-cmark's own (escaping) renderer.
+fn highlight_code_blocks(events: Vec<Event>) -> Vec<Event> {
+    let mut result = Vec::with_capacity(events.len());
+    let mut block: Option<(String, Vec<Event>)> = None;
+
+    for event in events {
+        match (event, &mut block) {
+            (event @ Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_))), None) => {
+                let Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) = &event else { unreachable!() };
+                let language = info.split([' ', ',']).next().unwrap_or_default().to_string();
+                block = Some((language, vec![event]));
+            }
+            (event @ Event::End(TagEnd::CodeBlock), Some(_)) => {
+                let (language, mut buffered) = block.take().unwrap_or_default();
+                let source: String = buffered.iter()
+                    .filter_map(|e| match e {
+                        Event::Text(text) => Some(text.as_ref()),
+                        _ => None,
+                    })
+                    .collect();
+
+                match highlight(&language, &source) {
+                    Some(html) => result.push(Event::Html(html.into())),
+                    None => {
+                        buffered.push(event);
+                        result.append(&mut buffered);
+                    }
+                }
+            }
+            (event, Some((_, buffered))) => buffered.push(event),
+            (event, None) => result.push(event),
+        }
+    }
+
+    result
+}
+
+fn highlight(language: &str, source: &str) -> Option<String> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(language)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+
+    for line in LinesWithEndings::from(source) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+
+    Some(format!("<pre><code class=\"language-{}\">{}</code></pre>\n", language, generator.finalize()))
+}
+
+/// Strips everything but formatting and highlighting markup; raw HTML in the Markdown
+/// source goes through the same filter.
+fn sanitize(html: &str) -> String {
+    ammonia::Builder::default()
+        .add_tag_attributes("code", &["class"])
+        .add_tag_attributes("span", &["class"])
+        .add_tag_attributes("input", &["type", "checked", "disabled"])
+        .add_tags(HashSet::from(["input"]))
+        .clean(html)
+        .to_string()
+}
+
+fn plain_text(events: &[Event]) -> String {
+    let mut text = String::new();
+
+    for event in events {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(t),
+            Event::End(TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link | TagEnd::Image) => {}
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn excerpt(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => &cut,
+    };
+
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let rendered = render("# Title\n\nSome *emphasis* and a [link](https://example.com).");
+
+        assert!(rendered.html.contains("<h1>Title</h1>"));
+        assert!(rendered.html.contains("<em>emphasis</em>"));
+        assert_eq!(rendered.excerpt, "Title Some emphasis and a link.");
+        assert_eq!(rendered.reading_time_minutes, 1);
+    }
+
+    #[test]
+    fn test_render_strips_scripts() {
+        let rendered = render("<script>alert(1)</script>\n\n<a href=\"javascript:alert(1)\" onclick=\"x()\">hi</a>");
+
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("javascript:"));
+        assert!(!rendered.html.contains("onclick"));
+    }
+
+    #[test]
+    fn test_render_highlights_code_fences() {
+        let rendered = render("```rust\nfn main() {}\n```\n\n```nope\n<b>\n```");
+
+        assert!(rendered.html.contains("<code class=\"language-rust\">"));
+        assert!(rendered.html.contains("<span class=\"storage type function rust\">fn</span>"));
+        assert!(rendered.html.contains("<code class=\"language-nope\">&lt;b&gt;"));
+    }
+
+    #[test]
+    fn test_excerpt_and_reading_time() {
+        let long = "word ".repeat(450);
+        let rendered = render(&long);
+
+        assert!(rendered.excerpt.ends_with("word…"));
+        assert!(rendered.excerpt.chars().count() <= EXCERPT_LENGTH + 1);
+        assert_eq!(rendered.reading_time_minutes, 3);
+    }
+}
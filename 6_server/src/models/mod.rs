@@ -1,2 +1,11 @@
 pub mod post;
 pub mod user;
+pub mod auth;
+pub mod page;
+pub mod search;
+pub mod comment;
+pub mod slug;
+pub mod tag;
+pub mod revision;
+pub mod markdown;
+pub mod health;
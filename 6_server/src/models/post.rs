@@ -1,19 +1,109 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
 use nutype::nutype;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::models::tag::{TagMatch, TagName};
+use crate::models::user::{Role, User};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Post {
     pub id: uuid::Uuid,
     pub title: Title,
+    pub slug: String,
+    /// Markdown source as written by the author.
     pub body: String,
-    pub published: bool,
+    pub body_html: String,
+    pub excerpt: String,
+    pub reading_time_minutes: i32,
+    pub status: PostStatus,
     pub author: uuid::Uuid,
+    pub tags: Vec<TagName>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Post {
+    pub fn can_be_edited_by(&self, user: &User) -> bool {
+        self.author == user.id || user.role >= Role::Editor
+    }
+
+    /// Posts that are not published yet only exist for the people who can edit them.
+    pub fn is_visible_to(&self, user: Option<&User>) -> bool {
+        self.status == PostStatus::Published || user.is_some_and(|user| self.can_be_edited_by(user))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    Draft,
+    InReview,
+    Scheduled,
+    Published,
+    Archived,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::InReview => "in_review",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Published => "published",
+            PostStatus::Archived => "archived",
+        }
+    }
+
+    /// draft → in review → scheduled → published → archived, where a reviewed post may also be
+    /// published right away and anything not yet published, or archived, can go back to draft.
+    pub fn can_transition_to(&self, next: PostStatus) -> bool {
+        use PostStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, InReview)
+                | (InReview, Draft | Scheduled | Published)
+                | (Scheduled, Draft | Published)
+                | (Published, Archived)
+                | (Archived, Draft)
+        )
+    }
+}
+
+impl Display for PostStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PostStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(PostStatus::Draft),
+            "in_review" => Ok(PostStatus::InReview),
+            "scheduled" => Ok(PostStatus::Scheduled),
+            "published" => Ok(PostStatus::Published),
+            "archived" => Ok(PostStatus::Archived),
+            _ => Err(anyhow::anyhow!("unknown post status: {}", s)),
+        }
+    }
 }
 
 #[nutype(
     sanitize(trim),
     validate(not_empty, len_char_max = 100, len_char_min = 3),
-    derive(Debug, PartialEq, Clone, Serialize, Deserialize, Display)
+    derive(Debug, PartialEq, Clone, Serialize, Deserialize, Display, AsRef)
 )]
 pub struct Title(String);
 
@@ -21,20 +111,135 @@ pub struct Title(String);
 pub struct CreatePost {
     pub title: Title,
     pub body: String,
-    pub author: uuid::Uuid,
+    #[serde(default)]
+    pub tags: Vec<TagName>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdatePost {
     pub title: Option<Title>,
     pub body: Option<String>,
-    pub published: Option<bool>,
-    pub author: Option<uuid::Uuid>,
+    pub tags: Option<Vec<TagName>>,
 }
 
 #[derive(Debug, Deserialize)]
+pub struct SchedulePost {
+    pub publish_at: DateTime<Utc>,
+}
+
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct PostFilter {
     pub title: Option<String>,
-    pub published: Option<bool>,
+    pub status: Option<PostStatus>,
     pub author: Option<uuid::Uuid>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Comma separated tag names, combined according to `tag_match`.
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub tag_match: TagMatch,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub sort: PostSort,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl PostFilter {
+    pub fn page_size(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn tag_names(&self) -> Vec<TagName> {
+        self.tags.as_deref().unwrap_or_default()
+            .split(',')
+            .filter_map(|name| TagName::try_new(name).ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    #[default]
+    CreatedAt,
+    /// Lists only posts that have been published at some point.
+    PublishedAt,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Position of the last post of a page, handed to clients as an opaque token.
+/// Carrying the sort key together with the id keeps pages stable when posts
+/// are inserted between requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostCursor {
+    pub sort: PostSort,
+    pub key: String,
+    pub id: uuid::Uuid,
+}
+
+impl PostCursor {
+    pub fn after(post: &Post, sort: PostSort) -> Self {
+        let key = match sort {
+            PostSort::CreatedAt => post.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            PostSort::PublishedAt => post.published_at
+                .map(|published| published.to_rfc3339_opts(SecondsFormat::Micros, true))
+                .unwrap_or_default(),
+            PostSort::Title => post.title.to_string(),
+        };
+        PostCursor { sort, key, id: post.id }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("cursor is serializable"))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(PostStatus::Draft.can_transition_to(PostStatus::InReview));
+        assert!(PostStatus::InReview.can_transition_to(PostStatus::Scheduled));
+        assert!(PostStatus::Scheduled.can_transition_to(PostStatus::Published));
+        assert!(PostStatus::Published.can_transition_to(PostStatus::Archived));
+        assert!(PostStatus::Archived.can_transition_to(PostStatus::Draft));
+
+        assert!(!PostStatus::Draft.can_transition_to(PostStatus::Published));
+        assert!(!PostStatus::Draft.can_transition_to(PostStatus::Draft));
+        assert!(!PostStatus::Published.can_transition_to(PostStatus::Draft));
+        assert!(!PostStatus::Archived.can_transition_to(PostStatus::Published));
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = PostCursor {
+            sort: PostSort::Title,
+            key: "Hello, world".to_string(),
+            id: uuid::Uuid::from_bytes([7; 16]),
+        };
+
+        assert_eq!(PostCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(PostCursor::decode("not a cursor"), None);
+    }
 }
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::post::Title;
+
+/// Snapshot of a post's title and body as written by a single create, update or rollback.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PostRevision {
+    pub post_id: uuid::Uuid,
+    pub revision: i32,
+    pub title: Title,
+    pub body: String,
+    /// `None` once the editor's account has been deleted.
+    pub editor: Option<uuid::Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub line: String,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RevisionDiff {
+    pub from: i32,
+    pub to: i32,
+    pub title: Vec<DiffLine>,
+    pub body: Vec<DiffLine>,
+}
+
+impl RevisionDiff {
+    pub fn between(from: &PostRevision, to: &PostRevision) -> Self {
+        RevisionDiff {
+            from: from.revision,
+            to: to.revision,
+            title: diff_lines(from.title.as_ref(), to.title.as_ref()),
+            body: diff_lines(&from.body, &to.body),
+        }
+    }
+}
+
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    // without this a missing trailing newline would make an unchanged last line show up as replaced
+    let (old, new) = (with_trailing_newline(old), with_trailing_newline(new));
+
+    TextDiff::from_lines(&old, &new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            op: match change.tag() {
+                ChangeTag::Equal => DiffOp::Equal,
+                ChangeTag::Insert => DiffOp::Insert,
+                ChangeTag::Delete => DiffOp::Delete,
+            },
+            line: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect()
+}
+
+fn with_trailing_newline(text: &str) -> String {
+    match text.is_empty() || text.ends_with('\n') {
+        true => text.to_string(),
+        false => format!("{}\n", text),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gen_revision(revision: i32, title: &str, body: &str) -> PostRevision {
+        PostRevision {
+            post_id: uuid::Uuid::from_bytes([0; 16]),
+            revision,
+            title: Title::try_new(title).unwrap(),
+            body: body.to_string(),
+            editor: None,
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_diff_between_revisions() {
+        let diff = RevisionDiff::between(
+            &gen_revision(1, "Same title", "first\nsecond\nthird"),
+            &gen_revision(2, "Same title", "first\nchanged\nthird\nfourth"),
+        );
+
+        let line = |op, line: &str| DiffLine { op, line: line.to_string() };
+        assert_eq!(diff.title, vec![line(DiffOp::Equal, "Same title")]);
+        assert_eq!(diff.body, vec![
+            line(DiffOp::Equal, "first"),
+            line(DiffOp::Delete, "second"),
+            line(DiffOp::Insert, "changed"),
+            line(DiffOp::Equal, "third"),
+            line(DiffOp::Insert, "fourth"),
+        ]);
+    }
+}
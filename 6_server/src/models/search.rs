@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::post::{Post, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+
+const SNIPPET_WORDS: usize = 35;
+
+/// `q` uses web search syntax: `"quoted phrases"`, `-excluded` words and `or`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+impl SearchQuery {
+    pub fn page_size(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub post: Post,
+    pub rank: f32,
+    /// HTML-escaped excerpt of the body with matches wrapped in `<mark>` tags.
+    pub snippet: String,
+}
+
+/// One alternative of a web search query: words or phrases that must all occur and words that must not.
+#[derive(Debug, Default, PartialEq)]
+struct Alternative {
+    required: Vec<Vec<String>>,
+    excluded: Vec<String>,
+}
+
+/// Approximation of Postgres' `websearch_to_tsquery` for backends without full text search,
+/// matching whole words case-insensitively but without stemming or stop words.
+#[derive(Debug, PartialEq)]
+pub struct SearchTerms {
+    alternatives: Vec<Alternative>,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn contains_phrase(haystack: &[String], phrase: &[String]) -> bool {
+    haystack.windows(phrase.len()).any(|window| window == phrase)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl SearchTerms {
+    pub fn parse(q: &str) -> Self {
+        let mut alternatives = vec![Alternative::default()];
+
+        for (i, part) in q.split('"').enumerate() {
+            if i % 2 == 1 {
+                let phrase = words(part);
+                if !phrase.is_empty() {
+                    alternatives.last_mut().expect("at least one alternative").required.push(phrase);
+                }
+                continue;
+            }
+
+            for token in part.split_whitespace() {
+                let current = alternatives.last_mut().expect("at least one alternative");
+                if token.eq_ignore_ascii_case("or") {
+                    if !current.required.is_empty() {
+                        alternatives.push(Alternative::default());
+                    }
+                } else if let Some(excluded) = token.strip_prefix('-') {
+                    current.excluded.extend(words(excluded));
+                } else {
+                    current.required.extend(words(token).into_iter().map(|word| vec![word]));
+                }
+            }
+        }
+
+        alternatives.retain(|alternative| !alternative.required.is_empty());
+        SearchTerms { alternatives }
+    }
+
+    fn terms(&self) -> impl Iterator<Item = &String> {
+        self.alternatives.iter().flat_map(|alternative| alternative.required.iter().flatten())
+    }
+
+    /// Rank of `post` for these terms, `None` when it does not match.
+    pub fn rank(&self, post: &Post) -> Option<f32> {
+        let document = [words(post.title.as_ref()), words(&post.body)].concat();
+
+        let matching = self.alternatives.iter()
+            .filter(|alternative| alternative.required.iter().all(|phrase| contains_phrase(&document, phrase)))
+            .filter(|alternative| !alternative.excluded.iter().any(|word| document.contains(word)))
+            .flat_map(|alternative| alternative.required.iter().flatten())
+            .collect::<Vec<_>>();
+
+        if matching.is_empty() {
+            return None;
+        }
+
+        let hits = document.iter().filter(|word| matching.contains(word)).count() as f32;
+        Some(hits / (hits + document.len() as f32))
+    }
+
+    /// Up to `SNIPPET_WORDS` words of `body` around the first match, HTML-escaped with
+    /// matching words wrapped in `<mark>` tags.
+    pub fn snippet(&self, body: &str) -> String {
+        let terms: Vec<&String> = self.terms().collect();
+        let is_match = |word: &str| words(word).iter().any(|w| terms.contains(&w));
+
+        let body_words: Vec<&str> = body.split_whitespace().collect();
+        let first = body_words.iter().position(|word| is_match(word)).unwrap_or(0);
+        let start = first.saturating_sub(SNIPPET_WORDS / 3);
+
+        body_words.iter().skip(start).take(SNIPPET_WORDS)
+            .map(|word| match is_match(word) {
+                true => format!("<mark>{}</mark>", escape(word)),
+                false => escape(word),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_terms() {
+        let terms = SearchTerms::parse("\"borrowing rules\" -unsafe or lifetimes");
+
+        assert_eq!(terms.alternatives, vec![
+            Alternative {
+                required: vec![vec!["borrowing".to_string(), "rules".to_string()]],
+                excluded: vec!["unsafe".to_string()],
+            },
+            Alternative {
+                required: vec![vec!["lifetimes".to_string()]],
+                excluded: vec![],
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_snippet_marks_and_escapes() {
+        let terms = SearchTerms::parse("borrowing");
+
+        assert_eq!(terms.snippet("<b>Borrowing</b> rules"), "<mark>&lt;b&gt;Borrowing&lt;/b&gt;</mark> rules");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::models::post::Title;
+
+/// Lowercases `value` and collapses every run of non-alphanumeric characters
+/// into a single `-`, e.g. `"Hello, World!"` becomes `"hello-world"`.
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+
+    slug
+}
+
+/// Slug for a post titled `title`, made unique by appending `-2`, `-3`, ... for as long as
+/// `is_taken` reports the candidate as used.
+pub fn unique_post_slug<E>(title: &Title, mut is_taken: impl FnMut(&str) -> Result<bool, E>) -> Result<String, E> {
+    let base = match slugify(title.as_ref()) {
+        s if s.is_empty() => "post".to_string(),
+        s => s,
+    };
+
+    let mut n = 1;
+    loop {
+        let candidate = match n {
+            1 => base.clone(),
+            _ => format!("{}-{}", base, n),
+        };
+
+        if !is_taken(&candidate)? {
+            return Ok(candidate);
+        }
+        n += 1;
+    }
+}
+
+/// Percent-encodes everything but ASCII alphanumerics and `-`, so a slug can be put into a URL.
+pub fn encode_path_segment(segment: &str) -> String {
+    segment.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Rust -- 2024  "), "rust-2024");
+        assert_eq!(slugify("Crème Brûlée"), "crème-brûlée");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_unique_post_slug() {
+        let taken = ["hello-world", "hello-world-2"];
+        let slug = unique_post_slug::<()>(&Title::try_new("Hello, World").unwrap(), |c| Ok(taken.contains(&c)));
+        assert_eq!(slug, Ok("hello-world-3".to_string()));
+
+        let slug = unique_post_slug::<()>(&Title::try_new("???").unwrap(), |_| Ok(false));
+        assert_eq!(slug, Ok("post".to_string()));
+    }
+}
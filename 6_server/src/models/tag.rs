@@ -0,0 +1,25 @@
+use nutype::nutype;
+use serde::{Deserialize, Serialize};
+
+use crate::models::slug::slugify;
+
+#[nutype(
+    sanitize(with = |name: String| slugify(&name)),
+    validate(not_empty, len_char_max = 32),
+    derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Display)
+)]
+pub struct TagName(String);
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TagCount {
+    pub name: TagName,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
@@ -1,12 +1,27 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use email_address::EmailAddress;
 use nutype::nutype;
 use serde::{Deserialize, Serialize};
 
+use crate::models::auth::Password;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct User {
     pub id: uuid::Uuid,
     pub username: Username,
     pub email: EmailAddress,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl User {
+    pub fn can_manage(&self, user_id: uuid::Uuid) -> bool {
+        self.id == user_id || self.role >= Role::Admin
+    }
 }
 
 #[nutype(
@@ -16,8 +31,66 @@ pub struct User {
 )]
 pub struct Username(String);
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Reader,
+    Author,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Author => "author",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reader" => Ok(Role::Reader),
+            "author" => Ok(Role::Author),
+            "editor" => Ok(Role::Editor),
+            "admin" => Ok(Role::Admin),
+            _ => Err(anyhow::anyhow!("unknown role: {}", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct CreateUser {
     pub name: Username,
     pub email: EmailAddress,
+    pub password: Password,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UpdateUser {
+    pub name: Option<Username>,
+    pub email: Option<EmailAddress>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UserFilter {
+    pub username: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UpdateRole {
+    pub role: Role,
 }
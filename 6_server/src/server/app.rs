@@ -1,7 +1,18 @@
 use axum::{middleware, Router};
 
-use crate::server::middlewares::tracing::tracing_middleware;
+use crate::data::data_errors::DataError;
+use crate::server::middlewares::metrics::http_metrics_middleware;
+use crate::server::middlewares::request_id::request_id_middleware;
+use crate::server::middlewares::tracing::{record_route, tracing_middleware};
+use crate::server::routers::auth_router::auth_router;
+use crate::server::routers::comment_router::comment_router;
+use crate::server::routers::feed_router::feed_router;
+use crate::server::routers::health_router::health_router;
+use crate::server::routers::metrics_router::metrics_router;
+use crate::server::routers::page_router::page_router;
 use crate::server::routers::post_router::post_router;
+use crate::server::routers::revision_router::revision_router;
+use crate::server::routers::tag_router::tag_router;
 use crate::server::routers::user_router::user_router;
 use crate::server::state::AppState;
 
@@ -9,7 +20,202 @@ pub fn define_app(state: AppState) -> Router {
     Router::new()
         .nest("/api",
               Router::new()
+                  .nest("/auth", auth_router(state.clone()))
                   .nest("/user", user_router(state.clone()))
-                  .nest("/post", post_router(state)))
+                  .nest("/post/:id/comments", comment_router(state.clone()))
+                  .nest("/post/:id/revisions", revision_router(state.clone()))
+                  .nest("/post", post_router(state.clone()))
+                  .nest("/tag", tag_router(state.clone()))
+                  .fallback(|| async { DataError::NotFound }))
+        .merge(feed_router(state.clone()))
+        .merge(metrics_router(state.clone()))
+        .merge(health_router(state.clone()))
+        .merge(page_router(state))
+        .route_layer(middleware::from_fn(record_route))
+        .layer(middleware::from_fn(http_metrics_middleware))
         .layer(middleware::from_fn(tracing_middleware))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+
+#[cfg(test)]
+mod test {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use crate::data::memory::in_memory::InMemory;
+    use crate::data::repositories::user_repository::UserRepository;
+    use crate::models::user::Role;
+
+    use super::*;
+
+    async fn send(app: &Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let body = match body {
+            Some(body) => {
+                request = request.header(CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+
+        let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    async fn create_author(app: &Router, repo: &InMemory, name: &str) -> String {
+        create_user(app, repo, name, Role::Author).await
+    }
+
+    async fn create_user(app: &Router, repo: &InMemory, name: &str, role: Role) -> String {
+        let (status, user) = send(app, "POST", "/api/user", None, Some(json!({
+            "name": name, "email": format!("{}@test.com", name), "password": "supersecret",
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let id = user["id"].as_str().unwrap().parse().unwrap();
+        repo.update_user_role(id, role).await.unwrap();
+
+        let (_, login) = send(app, "POST", "/api/auth/login", None, Some(json!({
+            "username": name, "password": "supersecret",
+        }))).await;
+        login["token"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_post_lifecycle_in_memory() {
+        let repo = InMemory::new();
+        let app = define_app(AppState::from(repo.clone()));
+        let token = create_author(&app, &repo, "alice").await;
+
+        let (status, post) = send(&app, "POST", "/api/post", Some(&token), Some(json!({
+            "title": "Hello, world", "body": "*hi*", "tags": ["Rust"],
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(post["slug"], "hello-world");
+        assert_eq!(post["body_html"], "<p><em>hi</em></p>\n");
+
+        let (status, page) = send(&app, "GET", "/api/post?tags=rust&status=draft", Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["items"][0]["id"], post["id"]);
+
+        let (status, _) = send(&app, "GET", "/api/post/by-slug/hello-world", None, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, page) = send(&app, "GET", "/api/post?tags=rust&status=draft", None, None).await;
+        assert_eq!(page["items"], json!([]));
+
+        let editor = create_user(&app, &repo, "bob", Role::Editor).await;
+        let id = post["id"].as_str().unwrap();
+        send(&app, "POST", &format!("/api/post/{}/submit", id), Some(&token), None).await;
+        let (status, _) = send(&app, "POST", &format!("/api/post/{}/publish", id), Some(&editor), None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(&app, "GET", "/api/post/by-slug/hello-world", None, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, page) = send(&app, "GET", "/api/post?tags=rust", None, None).await;
+        assert_eq!(page["items"][0]["id"], post["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_editor_restores_deleted_post() {
+        let repo = InMemory::new();
+        let app = define_app(AppState::from(repo.clone()));
+        let author = create_author(&app, &repo, "alice").await;
+        let editor = create_user(&app, &repo, "bob", Role::Editor).await;
+
+        let (_, post) = send(&app, "POST", "/api/post", Some(&author), Some(json!({
+            "title": "Deleted by mistake", "body": "body", "tags": [],
+        }))).await;
+        let id = post["id"].as_str().unwrap();
+        let (status, _) = send(&app, "DELETE", &format!("/api/post/{}", id), Some(&author), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, _) = send(&app, "POST", &format!("/api/post/{}/restore", id), Some(&author), None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, deleted) = send(&app, "GET", "/api/post/deleted", Some(&editor), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(deleted[0]["id"], id);
+
+        let (status, restored) = send(&app, "POST", &format!("/api/post/{}/restore", id), Some(&editor), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(restored["deleted_at"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_user_in_memory() {
+        let repo = InMemory::new();
+        let app = define_app(AppState::from(repo.clone()));
+        create_author(&app, &repo, "alice").await;
+
+        let (status, _) = send(&app, "POST", "/api/user", None, Some(json!({
+            "name": "alice", "email": "other@test.com", "password": "supersecret",
+        }))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_validation_error_is_problem_json() {
+        let repo = InMemory::new();
+        let app = define_app(AppState::from(repo.clone()));
+        let token = create_author(&app, &repo, "alice").await;
+
+        let request = Request::builder().method("POST").uri("/api/post")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/json")
+            .header("x-request-id", "req-42")
+            .body(Body::from(json!({ "title": "Hi", "body": "", "tags": [] }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/problem+json");
+        assert_eq!(response.headers()["x-request-id"], "req-42");
+        let problem: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(problem["code"], "validation_failed");
+        assert_eq!(problem["status"], 422);
+        assert_eq!(problem["request_id"], "req-42");
+        assert_eq!(problem["errors"][0]["field"], "title");
+
+        let (status, problem) = send(&app, "POST", "/api/post", Some(&token), Some(json!({ "body": "", "tags": [] }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["errors"][0]["field"], "title");
+
+        let (status, problem) = send(&app, "POST", "/api/user", None, Some(json!({
+            "name": "x", "email": "alice@test.com", "password": "short",
+        }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["errors"].as_array().unwrap().iter().map(|error| &error["field"]).collect::<Vec<_>>(), vec!["name", "password"]);
+
+        let (status, problem) = send(&app, "GET", "/api/post?tags=!!!", None, None).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["errors"][0]["field"], "tags[0]");
+
+        let (status, problem) = send(&app, "GET", "/api/post/not-a-uuid", None, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem["code"], "invalid_path");
+        assert!(problem["request_id"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_requests_by_route() {
+        let app = define_app(AppState::from(InMemory::new()));
+        send(&app, "GET", "/api/post/00000000-0000-0000-0000-000000000000", None, None).await;
+
+        let response = app.oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+
+        assert!(body.contains(r#"http_requests_total{method="GET",route="/api/post/:id",status="404"}"#));
+        assert!(body.contains(r#"http_request_duration_seconds_count{method="GET",route="/api/post/:id",status="404"}"#));
+        assert!(body.contains(r#"data_errors_total{code="not_found"}"#));
+    }
 }
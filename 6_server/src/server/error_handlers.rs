@@ -1,18 +1,143 @@
+use axum::http::header::CONTENT_TYPE;
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::{Html, IntoResponse, Response};
+use serde::Serialize;
 use tracing::error;
 
 use crate::data::data_errors::DataError;
+use crate::metrics::METRICS;
+use crate::server::middlewares::request_id::current_request_id;
+use crate::server::templates::ErrorPage;
 
-impl IntoResponse for DataError {
-    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+/// An RFC 7807 `application/problem+json` body, the shape of every error the API returns.
+#[derive(Debug, Serialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub title: &'static str,
+    pub status: u16,
+    pub detail: String,
+    /// Stable, machine readable identifier clients can match on, e.g. `validation_failed`.
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldViolation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// A single invalid field of a request body, e.g. `title` with `Title is too short...`.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FieldViolation {
+    pub field: String,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode, code: &'static str, detail: impl Into<String>) -> Self {
+        Problem {
+            kind: "about:blank",
+            title: status.canonical_reason().unwrap_or_default(),
+            status: status.as_u16(),
+            detail: detail.into(),
+            code,
+            errors: Vec::new(),
+            request_id: current_request_id(),
+        }
+    }
+
+    pub fn with_errors(self, errors: Vec<FieldViolation>) -> Self {
+        Problem { errors, ..self }
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::to_string(&self).unwrap_or_default();
+
+        (status, [(CONTENT_TYPE, "application/problem+json")], body).into_response()
+    }
+}
+
+impl From<DataError> for Problem {
+    fn from(err: DataError) -> Self {
+        err.record();
+        Problem::new(err.status_code(), err.code(), err.to_string())
+    }
+}
+
+impl DataError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            DataError::NotFound => StatusCode::NOT_FOUND,
+            DataError::Duplicate => StatusCode::CONFLICT,
+            DataError::Unauthorized => StatusCode::UNAUTHORIZED,
+            DataError::Forbidden => StatusCode::FORBIDDEN,
+            DataError::InvalidCursor => StatusCode::BAD_REQUEST,
+            DataError::UserHasPosts => StatusCode::CONFLICT,
+            DataError::InvalidTransition { .. } => StatusCode::CONFLICT,
+            DataError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
         match self {
-            DataError::NotFound => StatusCode::NOT_FOUND.into_response(),
-            DataError::Duplicate => StatusCode::CONFLICT.into_response(),
-            DataError::InternalServerError(_) => {
-                error!("Internal server error, {:?}", self);
-                StatusCode::INTERNAL_SERVER_ERROR.into_response()
-            }
+            DataError::NotFound => "not_found",
+            DataError::Duplicate => "duplicate",
+            DataError::Unauthorized => "unauthorized",
+            DataError::Forbidden => "forbidden",
+            DataError::InvalidCursor => "invalid_cursor",
+            DataError::UserHasPosts => "user_has_posts",
+            DataError::InvalidTransition { .. } => "invalid_transition",
+            DataError::InternalServerError(_) => "internal_error",
+        }
+    }
+
+    /// Counts every error answered to a client and logs the unexpected ones.
+    fn record(&self) {
+        METRICS.data_errors.with_label_values(&[self.code()]).inc();
+        if let DataError::InternalServerError(_) = self {
+            error!("Internal server error, {:?}", self);
+        }
+    }
+}
+
+impl IntoResponse for DataError {
+    fn into_response(self) -> Response {
+        Problem::from(self).into_response()
+    }
+}
+
+/// A `DataError` raised while serving an HTML page, answered with an error page
+/// instead of an empty body.
+#[derive(Debug)]
+pub struct PageError(pub DataError);
+
+impl From<DataError> for PageError {
+    fn from(err: DataError) -> Self {
+        PageError(err)
+    }
+}
+
+impl From<askama::Error> for PageError {
+    fn from(err: askama::Error) -> Self {
+        PageError(DataError::InternalServerError(err.into()))
+    }
+}
+
+impl IntoResponse for PageError {
+    fn into_response(self) -> Response {
+        self.0.record();
+
+        let status = self.0.status_code();
+        let page = ErrorPage {
+            status: status.as_u16(),
+            reason: status.canonical_reason().unwrap_or_default(),
+        };
+
+        match askama::Template::render(&page) {
+            Ok(html) => (status, Html(html)).into_response(),
+            Err(_) => status.into_response(),
         }
     }
-}
\ No newline at end of file
+}
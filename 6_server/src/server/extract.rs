@@ -0,0 +1,107 @@
+use axum::async_trait;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::server::error_handlers::{FieldViolation, Problem};
+use crate::server::validation::Validate;
+
+/// Drop-in replacements for axum's `Json`, `Path` and `Query` whose rejections are
+/// `Problem`s instead of plain text, so clients can parse every error the same way.
+pub struct Json<T>(pub T);
+
+pub struct Path<T>(pub T);
+
+pub struct Query<T>(pub T);
+
+/// Bodies are parsed in two steps: into `T::Raw`, which only fails on missing fields or
+/// wrong JSON types, then `T::validate` checks every field and reports all invalid ones.
+#[async_trait]
+impl<T: Validate, S: Send + Sync> FromRequest<S> for Json<T> {
+    type Rejection = Problem;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<serde_json::Value>::from_request(req, state).await?;
+        let raw = serde_path_to_error::deserialize::<_, T::Raw>(value).map_err(type_violation)?;
+
+        T::validate(raw).map(Json).map_err(|errors| {
+            Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", "The request body has invalid fields")
+                .with_errors(errors)
+        })
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+#[async_trait]
+impl<T: DeserializeOwned + Send, S: Send + Sync> FromRequestParts<S> for Path<T> {
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(rejection) => Err(Problem::from(rejection)),
+        }
+    }
+}
+
+/// Query strings are validated like bodies, after parsing into `T::Raw`.
+#[async_trait]
+impl<T: Validate, S: Send + Sync> FromRequestParts<S> for Query<T> {
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(raw) = axum::extract::Query::<T::Raw>::from_request_parts(parts, state).await?;
+
+        T::validate(raw).map(Query).map_err(|errors| {
+            Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", "The query string has invalid parameters")
+                .with_errors(errors)
+        })
+    }
+}
+
+impl From<JsonRejection> for Problem {
+    fn from(rejection: JsonRejection) -> Self {
+        let code = match rejection {
+            JsonRejection::JsonDataError(_) => "validation_failed",
+            JsonRejection::MissingJsonContentType(_) => "unsupported_media_type",
+            _ => "malformed_body",
+        };
+        Problem::new(rejection.status(), code, rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for Problem {
+    fn from(rejection: PathRejection) -> Self {
+        Problem::new(rejection.status(), "invalid_path", rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for Problem {
+    fn from(rejection: QueryRejection) -> Self {
+        Problem::new(rejection.status(), "invalid_query", rejection.body_text())
+    }
+}
+
+/// A body that is valid JSON but does not have the shape of `T::Raw`, e.g. a missing field.
+fn type_violation(err: serde_path_to_error::Error<serde_json::Error>) -> Problem {
+    let message = err.inner().to_string();
+    let field = match err.path().to_string() {
+        path if path == "." => message.strip_prefix("missing field `")
+            .and_then(|field| field.strip_suffix('`'))
+            .unwrap_or_default()
+            .to_string(),
+        path => path,
+    };
+
+    Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", err.to_string())
+        .with_errors(vec![FieldViolation { field, message }])
+}
@@ -0,0 +1,151 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{Duration, Utc};
+
+use crate::data::data_errors::DataError;
+use crate::models::auth::{CreateSession, LoginRequest, LoginResponse, SessionToken};
+use crate::server::extract::Json;
+use crate::server::middlewares::auth::bearer_token;
+use crate::services::{SessionRepositoryProvider, UserRepositoryProvider};
+
+const SESSION_TTL_HOURS: i64 = 24;
+
+/// Hash of a password nobody uses, checked when there is no hash of the user to check so an
+/// unknown username takes as long to reject as a wrong password.
+const DUMMY_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$924dDPJdk6ELmmK0i2evSQ$C1g/vee0iAHsi20qGWLSPESzfR7cjNOnLsLtSSbmloM";
+
+pub async fn login<S: UserRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    Json(body): Json<LoginRequest>,
+) -> axum::response::Result<Json<LoginResponse>> {
+    let credentials = match state.user_repository().get_user_credentials(&body.username).await {
+        Ok(credentials) => Some(credentials),
+        Err(DataError::NotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let password_hash = credentials.as_ref().and_then(|credentials| credentials.password_hash.clone());
+    let verified = body.password
+        .verify_blocking(password_hash.clone().unwrap_or_else(|| DUMMY_PASSWORD_HASH.to_string()))
+        .await
+        .map_err(DataError::InternalServerError)?
+        && password_hash.is_some();
+    let credentials = match credentials {
+        Some(credentials) if verified => credentials,
+        _ => return Err(DataError::Unauthorized.into()),
+    };
+
+    let token = SessionToken::generate();
+    let session = state.session_repository().create_session(CreateSession {
+        token_hash: token.hash(),
+        user_id: credentials.user.id,
+        expires_at: Utc::now() + Duration::hours(SESSION_TTL_HOURS),
+    }).await?;
+
+    Ok(Json(LoginResponse {
+        token: token.as_ref().to_string(),
+        expires_at: session.expires_at,
+    }))
+}
+
+pub async fn logout<S: SessionRepositoryProvider>(
+    State(state): State<S>,
+    headers: HeaderMap,
+) -> axum::response::Result<StatusCode> {
+    let token = bearer_token(&headers).ok_or(DataError::Unauthorized)?;
+    state.session_repository().delete_session(&token.hash()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use argon2::PasswordHash;
+    use axum::response::IntoResponse;
+
+    use crate::data::repositories::session_repository::MockSessionRepository;
+    use crate::data::repositories::user_repository::MockUserRepository;
+    use crate::models::auth::{Password, Session, UserCredentials};
+    use crate::models::user::{Role, Username};
+    use crate::server::test_support::{gen_test_user, MockProvider};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockUserRepository, &mut MockSessionRepository)) -> MockProvider {
+        let mut users = MockUserRepository::new();
+        let mut sessions = MockSessionRepository::new();
+        f(&mut users, &mut sessions);
+        MockProvider {
+            users: Arc::new(users),
+            sessions: Arc::new(sessions),
+            ..Default::default()
+        }
+    }
+
+    fn gen_credentials() -> UserCredentials {
+        UserCredentials {
+            user: gen_test_user(uuid::Uuid::from_bytes([0; 16]), Role::Reader),
+            password_hash: Some(Password::try_new("correct horse").unwrap().hash().unwrap()),
+        }
+    }
+
+    fn login_request(password: &str) -> LoginRequest {
+        LoginRequest {
+            username: Username::try_new("test").unwrap(),
+            password: Password::try_new(password).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login() {
+        let state = setup(|users, sessions| {
+            users.expect_get_user_credentials().returning(|_| Ok(gen_credentials()));
+            sessions.expect_create_session().returning(|s| Ok(Session {
+                user_id: s.user_id,
+                expires_at: s.expires_at,
+            }));
+        });
+
+        let response = login(State(state), Json(login_request("correct horse"))).await.unwrap();
+        assert_eq!(response.0.token.len(), 64);
+        assert!(response.0.expires_at > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password() {
+        let state = setup(|users, _| {
+            users.expect_get_user_credentials().returning(|_| Ok(gen_credentials()));
+        });
+
+        let response = login(State(state), Json(login_request("wrong horse"))).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_login_unknown_user() {
+        let state = setup(|users, _| {
+            users.expect_get_user_credentials().returning(|_| Err(DataError::NotFound));
+        });
+
+        let response = login(State(state), Json(login_request("correct horse"))).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_login_without_password_hash() {
+        let state = setup(|users, _| {
+            users.expect_get_user_credentials().returning(|_| Ok(UserCredentials { password_hash: None, ..gen_credentials() }));
+        });
+
+        let response = login(State(state), Json(login_request("correct horse"))).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_dummy_password_hash_rejects_passwords() {
+        assert!(PasswordHash::new(DUMMY_PASSWORD_HASH).is_ok());
+        assert!(!Password::try_new("correct horse").unwrap().verify(DUMMY_PASSWORD_HASH));
+    }
+}
@@ -0,0 +1,249 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Result;
+
+use crate::data::data_errors::DataError;
+use crate::models::comment::{Comment, CommentThread, CreateComment, ModerationStatus, UpdateCommentStatus, MAX_REPLY_DEPTH};
+use crate::server::error_handlers::{FieldViolation, Problem};
+use crate::server::extract::{Json, Path};
+use crate::server::handlers::post_handlers::visible_post;
+use crate::server::middlewares::auth::AuthUser;
+use crate::services::{CommentRepositoryProvider, PostRepositoryProvider, SessionRepositoryProvider};
+
+/// Anonymous readers only see approved comments; the post's author and editors see every comment.
+/// Comments of a post that is not published are as hidden as the post itself.
+pub async fn get_comments<S: CommentRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    user: Option<AuthUser>,
+    Path(post_id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<CommentThread>>> {
+    let user = user.map(|AuthUser(user)| user);
+    let post = visible_post(state.post_repository().get_post(post_id).await?, user.as_ref())?;
+
+    let status = match user {
+        Some(user) if post.can_be_edited_by(&user) => None,
+        _ => Some(ModerationStatus::Approved),
+    };
+
+    let comments = state.comment_repository().get_comments(post_id, status).await?;
+    Ok(Json(CommentThread::build(comments)))
+}
+
+pub async fn create_comment<S: CommentRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(post_id): Path<uuid::Uuid>,
+    Json(body): Json<CreateComment>,
+) -> Result<Json<Comment>> {
+    visible_post(state.post_repository().get_post(post_id).await?, Some(&user))?;
+
+    if let Some(parent) = body.parent_id {
+        if reply_depth(&state, parent).await? > MAX_REPLY_DEPTH {
+            return Err(Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", "Replies are nested too deep")
+                .with_errors(vec![FieldViolation {
+                    field: "parent_id".to_string(),
+                    message: format!("replies nest at most {} levels deep", MAX_REPLY_DEPTH),
+                }])
+                .into());
+        }
+    }
+
+    Ok(Json(state.comment_repository().create_comment(post_id, user.id, body).await?))
+}
+
+/// Levels a reply to `parent` would nest below its top-level comment, counting stops once
+/// it is past `MAX_REPLY_DEPTH`.
+async fn reply_depth<S: CommentRepositoryProvider>(state: &S, parent: uuid::Uuid) -> std::result::Result<usize, DataError> {
+    let mut depth = 1;
+    let mut ancestor = state.comment_repository().get_comment(parent).await?.parent_id;
+    while let Some(id) = ancestor.filter(|_| depth <= MAX_REPLY_DEPTH) {
+        depth += 1;
+        ancestor = state.comment_repository().get_comment(id).await?.parent_id;
+    }
+    Ok(depth)
+}
+
+pub async fn update_comment_status<S: CommentRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path((post_id, comment_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    Json(body): Json<UpdateCommentStatus>,
+) -> Result<Json<Comment>> {
+    let repository = state.comment_repository();
+
+    if repository.get_comment(comment_id).await?.post_id != post_id {
+        return Err(DataError::NotFound.into());
+    }
+
+    if !state.post_repository().get_post(post_id).await?.can_be_edited_by(&user) {
+        return Err(DataError::Forbidden.into());
+    }
+
+    Ok(Json(repository.update_comment_status(comment_id, body.status).await?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::response::IntoResponse;
+    use chrono::DateTime;
+
+    use crate::data::repositories::comment_repository::MockCommentRepository;
+    use crate::data::repositories::post_repository::MockPostRepository;
+    use crate::models::comment::CommentBody;
+    use crate::models::post::{Post, PostStatus};
+    use crate::models::user::Role;
+    use crate::server::test_support::{gen_test_post, gen_test_user, MockProvider, AUTHOR_ID, POST_ID};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockCommentRepository)) -> MockProvider {
+        let mut comments = MockCommentRepository::new();
+        f(&mut comments);
+        let mut posts = MockPostRepository::new();
+        posts.expect_get_post().returning(|_| Ok(Post { status: PostStatus::Published, ..gen_test_post() }));
+        MockProvider {
+            comments: Arc::new(comments),
+            posts: Arc::new(posts),
+            ..Default::default()
+        }
+    }
+
+    const COMMENT_ID: uuid::Uuid = uuid::Uuid::from_bytes([3; 16]);
+
+    fn gen_test_comment(post_id: uuid::Uuid) -> Comment {
+        Comment {
+            id: COMMENT_ID,
+            post_id,
+            author: uuid::Uuid::from_bytes([2; 16]),
+            parent_id: None,
+            body: CommentBody::try_new("Nice post").unwrap(),
+            status: ModerationStatus::Pending,
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+            updated_at: DateTime::from_timestamp(0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_comments_anonymous_only_sees_approved() {
+        let state = setup(|mock| {
+            mock.expect_get_comments()
+                .withf(|_, status| *status == Some(ModerationStatus::Approved))
+                .returning(|_, _| Ok(vec![]));
+        });
+
+        let response = get_comments(State(state), None, Path(POST_ID)).await.unwrap();
+        assert!(response.0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_comments_post_author_sees_all() {
+        let state = setup(|mock| {
+            mock.expect_get_comments()
+                .withf(|_, status| status.is_none())
+                .returning(|_, _| Ok(vec![gen_test_comment(POST_ID)]));
+        });
+
+        let response = get_comments(State(state), Some(AuthUser(gen_test_user(AUTHOR_ID, Role::Reader))), Path(POST_ID)).await.unwrap();
+        assert_eq!(response.0.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_comments_of_draft_post_are_not_found() {
+        let mut comments = MockCommentRepository::new();
+        comments.expect_get_comments().never();
+        comments.expect_create_comment().never();
+        let mut posts = MockPostRepository::new();
+        posts.expect_get_post().returning(|_| Ok(gen_test_post()));
+        let state = MockProvider { comments: Arc::new(comments), posts: Arc::new(posts), ..Default::default() };
+
+        let response = get_comments(State(state.clone()), None, Path(POST_ID)).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let reader = AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Reader));
+        let body = CreateComment { body: CommentBody::try_new("First").unwrap(), parent_id: None };
+        let response = create_comment(State(state), reader, Path(POST_ID), Json(body)).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_nested_too_deep_is_rejected() {
+        let state = setup(|mock| {
+            // every comment answers another one, the chain is deeper than any allowed thread
+            mock.expect_get_comment().returning(|id| Ok(Comment {
+                id,
+                parent_id: Some(uuid::Uuid::from_u128(id.as_u128() + 1)),
+                ..gen_test_comment(POST_ID)
+            }));
+            mock.expect_create_comment().never();
+        });
+
+        let body = CreateComment { body: CommentBody::try_new("Me too").unwrap(), parent_id: Some(COMMENT_ID) };
+        let response = create_comment(State(state), AuthUser(gen_test_user(AUTHOR_ID, Role::Reader)), Path(POST_ID), Json(body)).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_reply() {
+        let state = setup(|mock| {
+            mock.expect_get_comment().returning(|_| Ok(gen_test_comment(POST_ID)));
+            mock.expect_create_comment()
+                .returning(|_, _, body| Ok(Comment { parent_id: body.parent_id, ..gen_test_comment(POST_ID) }));
+        });
+
+        let body = CreateComment { body: CommentBody::try_new("Me too").unwrap(), parent_id: Some(COMMENT_ID) };
+        let response = create_comment(State(state), AuthUser(gen_test_user(AUTHOR_ID, Role::Reader)), Path(POST_ID), Json(body)).await.unwrap();
+        assert_eq!(response.0.parent_id, Some(COMMENT_ID));
+    }
+
+    #[tokio::test]
+    async fn test_update_comment_status_by_post_author() {
+        let state = setup(|mock| {
+            mock.expect_get_comment().returning(|_| Ok(gen_test_comment(POST_ID)));
+            mock.expect_update_comment_status()
+                .returning(|_, status| Ok(Comment { status, ..gen_test_comment(POST_ID) }));
+        });
+
+        let response = update_comment_status(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Reader)),
+            Path((POST_ID, COMMENT_ID)),
+            Json(UpdateCommentStatus { status: ModerationStatus::Approved }),
+        ).await.unwrap();
+        assert_eq!(response.0.status, ModerationStatus::Approved);
+    }
+
+    #[tokio::test]
+    async fn test_update_comment_status_by_other_user_is_forbidden() {
+        let state = setup(|mock| {
+            mock.expect_get_comment().returning(|_| Ok(gen_test_comment(POST_ID)));
+            mock.expect_update_comment_status().never();
+        });
+
+        let response = update_comment_status(
+            State(state),
+            AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Reader)),
+            Path((POST_ID, COMMENT_ID)),
+            Json(UpdateCommentStatus { status: ModerationStatus::Approved }),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_update_comment_status_of_other_post_is_not_found() {
+        let state = setup(|mock| {
+            mock.expect_get_comment().returning(|_| Ok(gen_test_comment(uuid::Uuid::from_bytes([9; 16]))));
+            mock.expect_update_comment_status().never();
+        });
+
+        let response = update_comment_status(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Reader)),
+            Path((POST_ID, COMMENT_ID)),
+            Json(UpdateCommentStatus { status: ModerationStatus::Approved }),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,243 @@
+use atom_syndication::{Content, Entry, Feed, Link, Person, Text};
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response, Result};
+use chrono::{DateTime, Utc};
+use rss::{Channel, Guid, Item};
+use sha2::{Digest, Sha256};
+
+use crate::config::SiteConfig;
+use crate::data::data_errors::DataError;
+use crate::models::post::{Post, PostFilter, PostSort, PostStatus};
+use crate::models::slug::encode_path_segment;
+use crate::services::{PostRepositoryProvider, SiteProvider, UserRepositoryProvider};
+
+pub const FEED_SIZE: i64 = 20;
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+pub async fn rss_feed<S: PostRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let site = state.site();
+    let posts = recent_posts(&state, None).await?;
+
+    let channel = Channel {
+        title: site.title.clone(),
+        link: site.url.clone(),
+        description: format!("Recent posts on {}", site.title),
+        last_build_date: Some(last_modified(&posts).to_rfc2822()),
+        items: posts.iter().map(|post| rss_item(&site, post)).collect(),
+        ..Default::default()
+    };
+
+    Ok(conditional_response(&headers, "application/rss+xml; charset=utf-8", channel.to_string(), last_modified(&posts)))
+}
+
+pub async fn atom_feed<S: PostRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let site = state.site();
+    let posts = recent_posts(&state, None).await?;
+    let feed = atom(&site, &site.title, &format!("{}/feed.atom", site.url), &posts);
+
+    Ok(conditional_response(&headers, "application/atom+xml; charset=utf-8", feed.to_string(), last_modified(&posts)))
+}
+
+pub async fn author_atom_feed<S: PostRepositoryProvider + UserRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let site = state.site();
+    let author = state.user_repository().get_user(id).await?;
+    let posts = recent_posts(&state, Some(id)).await?;
+    let feed = atom(&site, &author.username.to_string(), &format!("{}/api/user/{}/feed.atom", site.url, id), &posts);
+
+    Ok(conditional_response(&headers, "application/atom+xml; charset=utf-8", feed.to_string(), last_modified(&posts)))
+}
+
+async fn recent_posts<S: PostRepositoryProvider>(state: &S, author: Option<uuid::Uuid>) -> std::result::Result<Vec<Post>, DataError> {
+    Ok(state.post_repository().get_posts(PostFilter {
+        status: Some(PostStatus::Published),
+        author,
+        limit: Some(FEED_SIZE),
+        sort: PostSort::PublishedAt,
+        ..Default::default()
+    }).await?.items)
+}
+
+fn post_url(site: &SiteConfig, post: &Post) -> String {
+    format!("{}/posts/{}", site.url, encode_path_segment(&post.slug))
+}
+
+fn last_modified(posts: &[Post]) -> DateTime<Utc> {
+    posts.iter().map(|post| post.updated_at).max().unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+fn rss_item(site: &SiteConfig, post: &Post) -> Item {
+    Item {
+        title: Some(post.title.to_string()),
+        link: Some(post_url(site, post)),
+        description: Some(post.body_html.clone()),
+        guid: Some(Guid { value: post.id.to_string(), permalink: false }),
+        pub_date: post.published_at.map(|published| published.to_rfc2822()),
+        ..Default::default()
+    }
+}
+
+fn atom(site: &SiteConfig, title: &str, self_url: &str, posts: &[Post]) -> Feed {
+    Feed {
+        title: Text::plain(title),
+        id: self_url.to_string(),
+        updated: last_modified(posts).fixed_offset(),
+        authors: vec![Person { name: title.to_string(), ..Default::default() }],
+        links: vec![
+            Link { href: self_url.to_string(), rel: "self".to_string(), ..Default::default() },
+            Link { href: site.url.clone(), ..Default::default() },
+        ],
+        entries: posts.iter().map(|post| Entry {
+            title: Text::plain(post.title.to_string()),
+            id: format!("urn:uuid:{}", post.id),
+            updated: post.updated_at.fixed_offset(),
+            published: post.published_at.map(|published| published.fixed_offset()),
+            links: vec![Link { href: post_url(site, post), ..Default::default() }],
+            summary: Some(Text::plain(post.excerpt.clone())),
+            content: Some(Content {
+                value: Some(post.body_html.clone()),
+                content_type: Some("html".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }).collect(),
+        ..Default::default()
+    }
+}
+
+/// Answers `304 Not Modified` when the client's `If-None-Match` shows it already has this
+/// version of the document. `If-Modified-Since` is ignored, the newest `updated_at` of the
+/// listed posts moves backwards when a post drops out of the feed, e.g. once it is archived.
+/// Tags are compared weakly, proxies that compress the feed mark the tag they pass on as `W/`.
+fn conditional_response(headers: &HeaderMap, content_type: &'static str, body: String, modified: DateTime<Utc>) -> Response {
+    let etag = format!("\"{}\"", hex::encode(&Sha256::digest(body.as_bytes())[..16]));
+    let last_modified = modified.format(HTTP_DATE_FORMAT).to_string();
+
+    let not_modified = headers.get(header::IF_NONE_MATCH)
+        .and_then(|if_none_match| if_none_match.to_str().ok())
+        .is_some_and(|if_none_match| if_none_match.split(',')
+            .map(|tag| tag.trim())
+            .any(|tag| tag.strip_prefix("W/").unwrap_or(tag) == etag || tag == "*"));
+
+    let cache_headers = [(header::ETAG, etag), (header::LAST_MODIFIED, last_modified)];
+
+    match not_modified {
+        true => (StatusCode::NOT_MODIFIED, cache_headers).into_response(),
+        false => (cache_headers, [(header::CONTENT_TYPE, content_type)], body).into_response(),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+
+    use crate::data::repositories::post_repository::MockPostRepository;
+    use crate::data::repositories::user_repository::MockUserRepository;
+    use crate::models::page::Page;
+    use crate::models::user::{Role, User, Username};
+    use crate::server::test_support::{gen_test_post, gen_test_user, MockProvider, AUTHOR_ID};
+
+    use super::*;
+
+    fn setup() -> MockProvider {
+        let mut posts = MockPostRepository::new();
+        posts.expect_get_posts()
+            .withf(|filter| filter.status == Some(PostStatus::Published) && filter.limit == Some(FEED_SIZE) && filter.sort == PostSort::PublishedAt)
+            .returning(|filter| Ok(Page {
+                items: vec![gen_published_post(filter.author.unwrap_or(AUTHOR_ID))],
+                next_cursor: None,
+            }));
+        let mut users = MockUserRepository::new();
+        users.expect_get_user().returning(|id| Ok(User {
+            username: Username::try_new("alice").unwrap(),
+            ..gen_test_user(id, Role::Author)
+        }));
+        MockProvider {
+            posts: Arc::new(posts),
+            users: Arc::new(users),
+            ..Default::default()
+        }
+    }
+
+    fn gen_published_post(author: uuid::Uuid) -> Post {
+        Post {
+            status: PostStatus::Published,
+            author,
+            updated_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            published_at: DateTime::from_timestamp(1_700_000_000, 0),
+            ..gen_test_post()
+        }
+    }
+
+    async fn body_string(response: Response) -> String {
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rss_feed() {
+        let response = rss_feed(State(setup()), HeaderMap::new()).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::LAST_MODIFIED], "Tue, 14 Nov 2023 22:13:20 GMT");
+        let body = body_string(response).await;
+        assert!(body.contains("<link>https://blog.test/posts/test-post</link>"));
+        assert!(body.contains("<pubDate>Tue, 14 Nov 2023 22:13:20 +0000</pubDate>"));
+    }
+
+    #[test]
+    fn test_post_url_encodes_slug() {
+        let site = SiteConfig { url: "https://blog.test".to_string(), title: "Test blog".to_string() };
+        let post = Post { slug: "crème brûlée?".to_string(), ..gen_test_post() };
+
+        assert_eq!(post_url(&site, &post), "https://blog.test/posts/cr%C3%A8me%20br%C3%BBl%C3%A9e%3F");
+    }
+
+    #[tokio::test]
+    async fn test_author_atom_feed() {
+        let response = author_atom_feed(State(setup()), Path(AUTHOR_ID), HeaderMap::new()).await.unwrap();
+
+        let body = body_string(response).await;
+        assert!(body.contains("<title>alice</title>"));
+        assert!(body.contains("<id>urn:uuid:00000000-0000-0000-0000-000000000000</id>"));
+    }
+
+    #[tokio::test]
+    async fn test_feed_not_modified() {
+        let response = atom_feed(State(setup()), HeaderMap::new()).await.unwrap();
+        let etag = response.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = atom_feed(State(setup()), headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, format!("\"other\", W/{}", etag.to_str().unwrap()).parse().unwrap());
+        let response = atom_feed(State(setup()), headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MODIFIED_SINCE, "Tue, 14 Nov 2023 22:13:20 GMT".parse().unwrap());
+        let response = atom_feed(State(setup()), headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, "\"stale\"".parse().unwrap());
+        let response = atom_feed(State(setup()), headers).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::data::data_errors::DataError;
+use crate::models::health::{DatabaseHealth, HealthStatus, MigrationsHealth, PoolHealth, Readiness};
+use crate::services::HealthRepositoryProvider;
+
+/// Upper bound for each readiness check, a saturated pool would otherwise hold the probe
+/// for the whole connection timeout.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Liveness only proves the process serves requests, it never touches the database so an
+/// outage does not get healthy replicas restarted.
+pub async fn healthz() -> Json<Value> {
+    Json(json!({ "status": HealthStatus::Ok }))
+}
+
+pub async fn readyz<S: HealthRepositoryProvider>(
+    State(state): State<S>,
+) -> (StatusCode, Json<Readiness>) {
+    let repository = state.health_repository();
+
+    let start = Instant::now();
+    let ping = tokio::time::timeout(CHECK_TIMEOUT, repository.ping()).await;
+    let database = DatabaseHealth {
+        status: HealthStatus::Ok,
+        latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+        error: None,
+    };
+    let database = match ping {
+        Ok(Ok(())) => database,
+        Ok(Err(e)) => DatabaseHealth { status: HealthStatus::Degraded, error: Some(failure(e)), ..database },
+        Err(_) => DatabaseHealth { status: HealthStatus::Degraded, error: Some("timed out".to_string()), ..database },
+    };
+
+    let migrations = match tokio::time::timeout(CHECK_TIMEOUT, repository.pending_migrations()).await {
+        Ok(Ok(pending)) if pending.is_empty() => MigrationsHealth { status: HealthStatus::Ok, pending, error: None },
+        Ok(Ok(pending)) => MigrationsHealth { status: HealthStatus::Degraded, pending, error: None },
+        Ok(Err(e)) => MigrationsHealth { status: HealthStatus::Degraded, pending: Vec::new(), error: Some(failure(e)) },
+        Err(_) => MigrationsHealth { status: HealthStatus::Degraded, pending: Vec::new(), error: Some("timed out".to_string()) },
+    };
+
+    let readiness = Readiness::new(database, migrations, repository.pool_status().map(PoolHealth::from));
+    let status = match readiness.status {
+        HealthStatus::Ok => StatusCode::OK,
+        HealthStatus::Degraded => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(readiness))
+}
+
+fn failure(err: DataError) -> String {
+    warn!("Readiness check failed, {:?}", err);
+    err.to_string()
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::data::repositories::health_repository::MockHealthRepository;
+    use crate::models::health::PoolStatus;
+    use crate::server::test_support::MockProvider;
+
+    use super::*;
+
+    fn mock(ping_ok: bool, pending: &'static [&'static str], in_use: u32) -> MockProvider {
+        let mut mock = MockHealthRepository::new();
+        mock.expect_ping().returning(move || match ping_ok {
+            true => Ok(()),
+            false => Err(DataError::InternalServerError(anyhow::anyhow!("connection refused"))),
+        });
+        mock.expect_pending_migrations().returning(move || Ok(pending.iter().map(|name| name.to_string()).collect()));
+        mock.expect_pool_status().returning(move || Some(PoolStatus { max_size: 10, connections: 10, idle: 10 - in_use }));
+        MockProvider { health: Arc::new(mock), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_readyz_ok() {
+        let (status, Json(readiness)) = readyz(State(mock(true, &[], 3))).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(readiness.status, HealthStatus::Ok);
+        assert_eq!(readiness.pool.unwrap().in_use, 3);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_degraded() {
+        let (status, Json(readiness)) = readyz(State(mock(false, &[], 0))).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(readiness.database.status, HealthStatus::Degraded);
+        assert_eq!(readiness.migrations.status, HealthStatus::Ok);
+
+        let (status, Json(readiness)) = readyz(State(mock(true, &["2024-09-16-103000_render_post_bodies"], 0))).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(readiness.migrations.pending, vec!["2024-09-16-103000_render_post_bodies"]);
+
+        let (status, Json(readiness)) = readyz(State(mock(true, &[], 10))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(readiness.status, HealthStatus::Ok);
+        assert_eq!(readiness.pool.unwrap().status, HealthStatus::Degraded);
+    }
+}
@@ -0,0 +1,17 @@
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+
+use crate::data::data_errors::DataError;
+use crate::metrics::METRICS;
+use crate::services::HealthRepositoryProvider;
+
+pub async fn metrics<S: HealthRepositoryProvider>(
+    State(state): State<S>,
+) -> Result<impl IntoResponse, DataError> {
+    if let Some(status) = state.health_repository().pool_status() {
+        METRICS.set_pool_status(status);
+    }
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], METRICS.encode()?))
+}
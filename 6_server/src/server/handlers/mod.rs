@@ -1,2 +1,11 @@
 pub mod user_handlers;
-pub mod post_handlers;
\ No newline at end of file
+pub mod post_handlers;
+pub mod auth_handlers;
+pub mod search_handlers;
+pub mod comment_handlers;
+pub mod tag_handlers;
+pub mod revision_handlers;
+pub mod feed_handlers;
+pub mod page_handlers;
+pub mod metrics_handlers;
+pub mod health_handlers;
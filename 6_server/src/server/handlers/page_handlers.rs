@@ -0,0 +1,182 @@
+use askama::Template;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+
+use crate::data::data_errors::DataError;
+use crate::models::page::Page;
+use crate::models::post::{Post, PostFilter, PostSort, PostStatus};
+use crate::models::slug::encode_path_segment;
+use crate::server::error_handlers::PageError;
+use crate::server::templates::{AuthorPage, IndexPage, PostPage};
+use crate::services::{PostRepositoryProvider, SiteProvider, UserRepositoryProvider};
+
+pub const POSTS_PER_PAGE: i64 = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub cursor: Option<String>,
+}
+
+async fn published_posts<S: PostRepositoryProvider>(state: &S, author: Option<uuid::Uuid>, cursor: Option<String>) -> Result<Page<Post>, DataError> {
+    state.post_repository().get_posts(PostFilter {
+        status: Some(PostStatus::Published),
+        author,
+        limit: Some(POSTS_PER_PAGE),
+        cursor,
+        sort: PostSort::PublishedAt,
+        ..Default::default()
+    }).await
+}
+
+pub async fn index_page<S: PostRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    Query(query): Query<PageQuery>,
+) -> Result<Html<String>, PageError> {
+    let page = published_posts(&state, None, query.cursor).await?;
+
+    Ok(Html(IndexPage {
+        site: &state.site(),
+        posts: &page.items,
+        next_cursor: page.next_cursor,
+    }.render()?))
+}
+
+/// Only published posts are visible here; old slugs redirect to the current one.
+pub async fn post_page<S: PostRepositoryProvider + UserRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    Path(slug): Path<String>,
+) -> Result<Response, PageError> {
+    let post = state.post_repository().get_post_by_slug(&slug).await?;
+
+    if post.status != PostStatus::Published {
+        return Err(DataError::NotFound.into());
+    }
+
+    if post.slug != slug {
+        let location = format!("/posts/{}", encode_path_segment(&post.slug));
+        return Ok((StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)]).into_response());
+    }
+
+    let author = state.user_repository().get_user(post.author).await?;
+
+    Ok(Html(PostPage {
+        site: &state.site(),
+        post: &post,
+        author: &author,
+    }.render()?).into_response())
+}
+
+pub async fn author_page<S: PostRepositoryProvider + UserRepositoryProvider + SiteProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<PageQuery>,
+) -> Result<Html<String>, PageError> {
+    let author = state.user_repository().get_user(id).await?;
+    let page = published_posts(&state, Some(id), query.cursor).await?;
+
+    Ok(Html(AuthorPage {
+        site: &state.site(),
+        author: &author,
+        posts: &page.items,
+        next_cursor: page.next_cursor,
+    }.render()?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+    use chrono::DateTime;
+
+    use crate::data::repositories::post_repository::MockPostRepository;
+    use crate::data::repositories::user_repository::MockUserRepository;
+    use crate::models::post::Title;
+    use crate::models::user::{Role, User, Username};
+    use crate::server::test_support::{gen_test_post, gen_test_user, MockProvider};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockPostRepository)) -> MockProvider {
+        let mut posts = MockPostRepository::new();
+        f(&mut posts);
+        let mut users = MockUserRepository::new();
+        users.expect_get_user().returning(|id| Ok(User {
+            username: Username::try_new("alice").unwrap(),
+            ..gen_test_user(id, Role::Author)
+        }));
+        MockProvider {
+            posts: Arc::new(posts),
+            users: Arc::new(users),
+            ..Default::default()
+        }
+    }
+
+    fn gen_published_post() -> Post {
+        Post {
+            title: Title::try_new("<b>Tags</b> & titles").unwrap(),
+            slug: "b-tags-b-titles".to_string(),
+            body: "**body**".to_string(),
+            body_html: "<p><strong>body</strong></p>".to_string(),
+            status: PostStatus::Published,
+            published_at: DateTime::from_timestamp(1_700_000_000, 0),
+            ..gen_test_post()
+        }
+    }
+
+    async fn body_string(response: Response) -> String {
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_index_page_escapes_titles_and_links_next_page() {
+        let state = setup(|mock| {
+            mock.expect_get_posts()
+                .withf(|filter| filter.status == Some(PostStatus::Published) && filter.sort == PostSort::PublishedAt && filter.cursor.is_none())
+                .returning(|_| Ok(Page { items: vec![gen_published_post()], next_cursor: Some("next".to_string()) }));
+        });
+
+        let body = body_string(index_page(State(state), Query(PageQuery { cursor: None })).await.unwrap().into_response()).await;
+        assert!(body.contains("&lt;b&gt;Tags&lt;/b&gt; &amp; titles"));
+        assert!(body.contains("November 14, 2023"));
+        assert!(body.contains("href=\"/?cursor=next\""));
+    }
+
+    #[tokio::test]
+    async fn test_post_page_renders_body_html() {
+        let state = setup(|mock| {
+            mock.expect_get_post_by_slug().returning(|_| Ok(gen_published_post()));
+        });
+
+        let response = post_page(State(state), Path("b-tags-b-titles".to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_string(response).await;
+        assert!(body.contains("<p><strong>body</strong></p>"));
+        assert!(body.contains("href=\"/authors/01010101-0101-0101-0101-010101010101\""));
+    }
+
+    #[tokio::test]
+    async fn test_post_page_redirects_old_slug() {
+        let state = setup(|mock| {
+            mock.expect_get_post_by_slug().returning(|_| Ok(gen_published_post()));
+        });
+
+        let response = post_page(State(state), Path("old-slug".to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[header::LOCATION], "/posts/b-tags-b-titles");
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_post_page_is_not_found() {
+        let state = setup(|mock| {
+            mock.expect_get_post_by_slug().returning(|_| Ok(Post { status: PostStatus::Draft, ..gen_published_post() }));
+        });
+
+        let response = post_page(State(state), Path("b-tags-b-titles".to_string())).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(body_string(response).await.contains("404 Not Found"));
+    }
+}
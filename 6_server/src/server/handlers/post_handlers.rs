@@ -1,35 +1,453 @@
-use axum::extract::{Path, Query, State};
-use axum::Json;
-use axum::response::Result;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response, Result};
+use chrono::Utc;
 
-use crate::models::post::{CreatePost, Post, PostFilter, UpdatePost};
-use crate::services::PostRepositoryProvider;
+use crate::data::data_errors::DataError;
+use crate::models::page::Page;
+use crate::models::post::{CreatePost, Post, PostFilter, PostStatus, SchedulePost, UpdatePost};
+use crate::models::slug::encode_path_segment;
+use crate::models::user::{Role, User};
+use crate::server::error_handlers::{FieldViolation, Problem};
+use crate::server::extract::{Json, Path, Query};
+use crate::server::middlewares::auth::AuthUser;
+use crate::services::{PostRepositoryProvider, SessionRepositoryProvider};
 
-pub async fn create_post<S: PostRepositoryProvider>(
+pub async fn create_post<S: PostRepositoryProvider + SessionRepositoryProvider>(
     State(state): State<S>,
+    AuthUser(user): AuthUser,
     Json(body): Json<CreatePost>,
 ) -> Result<Json<Post>> {
-    Ok(Json(state.post_repository().create_post(body)?))
+    Ok(Json(state.post_repository().create_post(user.id, body).await?))
 }
 
-pub async fn get_post<S: PostRepositoryProvider>(
+pub async fn get_post<S: PostRepositoryProvider + SessionRepositoryProvider>(
     State(state): State<S>,
+    user: Option<AuthUser>,
     Path(id): Path<uuid::Uuid>,
 ) -> Result<Json<Post>> {
-    Ok(Json(state.post_repository().get_post(id)?))
+    let user = user.map(|AuthUser(user)| user);
+    Ok(Json(visible_post(state.post_repository().get_post(id).await?, user.as_ref())?))
 }
 
-pub async fn get_all_posts<S: PostRepositoryProvider>(
+/// Answers with `301 Moved Permanently` and the post's current slug in `Location`
+/// when `slug` is one the post has since been renamed from.
+pub async fn get_post_by_slug<S: PostRepositoryProvider + SessionRepositoryProvider>(
     State(state): State<S>,
-    Query(filter): Query<PostFilter>,
-) -> Result<Json<Vec<Post>>> {
-    Ok(Json(state.post_repository().get_posts(filter)?))
+    user: Option<AuthUser>,
+    Path(slug): Path<String>,
+) -> Result<Response> {
+    let user = user.map(|AuthUser(user)| user);
+    let post = visible_post(state.post_repository().get_post_by_slug(&slug).await?, user.as_ref())?;
+
+    if post.slug != slug {
+        // relative to `.../by-slug/{old}`, so it resolves regardless of where the router is nested
+        let location = encode_path_segment(&post.slug);
+        return Ok((StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)], Json(post)).into_response());
+    }
+
+    Ok(Json(post).into_response())
 }
 
-pub async fn update_post<S: PostRepositoryProvider>(
+/// Callers below `Author` only list published posts. Authors asking for another status only
+/// get their own posts, editors get every post.
+pub async fn get_all_posts<S: PostRepositoryProvider + SessionRepositoryProvider>(
     State(state): State<S>,
+    user: Option<AuthUser>,
+    Query(mut filter): Query<PostFilter>,
+) -> Result<Json<Page<Post>>> {
+    match user {
+        Some(AuthUser(user)) if user.role >= Role::Editor => {}
+        Some(AuthUser(user)) if user.role >= Role::Author && filter.status.is_some_and(|status| status != PostStatus::Published) => {
+            filter.author = Some(user.id);
+        }
+        _ => filter.status = Some(PostStatus::Published),
+    }
+
+    Ok(Json(state.post_repository().get_posts(filter).await?))
+}
+
+/// Unpublished posts are reported missing to everyone who cannot edit them.
+pub(crate) fn visible_post(post: Post, user: Option<&User>) -> std::result::Result<Post, DataError> {
+    match post.is_visible_to(user) {
+        true => Ok(post),
+        false => Err(DataError::NotFound),
+    }
+}
+
+pub async fn update_post<S: PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
     Path(id): Path<uuid::Uuid>,
     Json(body): Json<UpdatePost>,
 ) -> Result<Json<Post>> {
-    Ok(Json(state.post_repository().update_post(id, body)?))
-}
\ No newline at end of file
+    let repository = state.post_repository();
+
+    if !repository.get_post(id).await?.can_be_edited_by(&user) {
+        return Err(DataError::Forbidden.into());
+    }
+
+    Ok(Json(repository.update_post(id, user.id, body).await?))
+}
+
+pub async fn delete_post<S: PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<StatusCode> {
+    let repository = state.post_repository();
+
+    if !repository.get_post(id).await?.can_be_edited_by(&user) {
+        return Err(DataError::Forbidden.into());
+    }
+
+    repository.delete_post(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Open to editors rather than admins only, editors delete posts by mistake and need to
+/// find and restore them without waiting for an admin.
+pub async fn get_deleted_posts<S: PostRepositoryProvider>(
+    State(state): State<S>,
+) -> Result<Json<Vec<Post>>> {
+    Ok(Json(state.post_repository().get_deleted_posts().await?))
+}
+
+pub async fn restore_post<S: PostRepositoryProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>> {
+    Ok(Json(state.post_repository().restore_post(id).await?))
+}
+
+/// Hands a draft over to the editors.
+pub async fn submit_post<S: PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>> {
+    Ok(Json(transition_own_post(state, user, id, PostStatus::InReview).await?))
+}
+
+pub async fn return_post_to_draft<S: PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>> {
+    Ok(Json(transition_own_post(state, user, id, PostStatus::Draft).await?))
+}
+
+async fn transition_own_post<S: PostRepositoryProvider>(state: S, user: User, id: uuid::Uuid, status: PostStatus) -> std::result::Result<Post, DataError> {
+    let repository = state.post_repository();
+
+    if !repository.get_post(id).await?.can_be_edited_by(&user) {
+        return Err(DataError::Forbidden);
+    }
+
+    repository.transition_post(id, status, None).await
+}
+
+/// `publish_at` has to lie in the future, the publishing job would otherwise pick the post up
+/// on its next run and it would never have been scheduled at all.
+pub async fn schedule_post<S: PostRepositoryProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+    Json(body): Json<SchedulePost>,
+) -> Result<Json<Post>> {
+    if body.publish_at <= Utc::now() {
+        return Err(Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", "publish_at must be in the future")
+            .with_errors(vec![FieldViolation {
+                field: "publish_at".to_string(),
+                message: "must be in the future".to_string(),
+            }])
+            .into());
+    }
+
+    Ok(Json(state.post_repository().transition_post(id, PostStatus::Scheduled, Some(body.publish_at)).await?))
+}
+
+pub async fn publish_post<S: PostRepositoryProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>> {
+    Ok(Json(state.post_repository().transition_post(id, PostStatus::Published, None).await?))
+}
+
+pub async fn archive_post<S: PostRepositoryProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>> {
+    Ok(Json(state.post_repository().transition_post(id, PostStatus::Archived, None).await?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use chrono::Duration;
+
+    use crate::data::repositories::post_repository::MockPostRepository;
+    use crate::models::post::Title;
+    use crate::server::test_support::{gen_test_post, gen_test_user, MockProvider, AUTHOR_ID};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockPostRepository)) -> MockProvider {
+        let mut posts = MockPostRepository::new();
+        f(&mut posts);
+        MockProvider {
+            posts: Arc::new(posts),
+            ..Default::default()
+        }
+    }
+
+    fn gen_update() -> UpdatePost {
+        UpdatePost {
+            title: None,
+            body: Some("new body".to_string()),
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_post_uses_caller_as_author() {
+        let state = setup(|mock| {
+            mock.expect_create_post()
+                .withf(|author, _| *author == AUTHOR_ID)
+                .returning(|_, _| Ok(gen_test_post()));
+        });
+
+        let post = CreatePost {
+            title: Title::try_new("Test post").unwrap(),
+            body: "body".to_string(),
+            tags: vec![],
+        };
+
+        let response = create_post(State(state), AuthUser(gen_test_user(AUTHOR_ID, Role::Author)), Json(post)).await.unwrap();
+        assert_eq!(response.0.author, AUTHOR_ID);
+    }
+
+    #[tokio::test]
+    async fn test_get_post_by_current_slug() {
+        let state = setup(|mock| {
+            mock.expect_get_post_by_slug()
+                .withf(|slug| slug == "test-post")
+                .returning(|_| Ok(Post { status: PostStatus::Published, ..gen_test_post() }));
+        });
+
+        let response = get_post_by_slug(State(state), None, Path("test-post".to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::LOCATION).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_post_by_old_slug_redirects() {
+        let state = setup(|mock| {
+            mock.expect_get_post_by_slug().returning(|_| Ok(Post {
+                slug: "crème-brûlée".to_string(),
+                status: PostStatus::Published,
+                ..gen_test_post()
+            }));
+        });
+
+        let response = get_post_by_slug(State(state), None, Path("old-title".to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[header::LOCATION], "cr%C3%A8me-br%C3%BBl%C3%A9e");
+    }
+
+    #[tokio::test]
+    async fn test_get_draft_post_anonymously_is_not_found() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_get_post_by_slug().returning(|_| Ok(gen_test_post()));
+        });
+
+        let response = get_post(State(state.clone()), None, Path(gen_test_post().id)).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = get_post_by_slug(State(state.clone()), None, Path("test-post".to_string())).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let reader = AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Reader));
+        let response = get_post(State(state.clone()), Some(reader), Path(gen_test_post().id)).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let author = AuthUser(gen_test_user(AUTHOR_ID, Role::Author));
+        let response = get_post(State(state), Some(author), Path(gen_test_post().id)).await.unwrap();
+        assert_eq!(response.0.status, PostStatus::Draft);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_posts_anonymously_lists_published_only() {
+        let state = setup(|mock| {
+            mock.expect_get_posts()
+                .withf(|filter| filter.status == Some(PostStatus::Published))
+                .returning(|_| Ok(Page { items: vec![], next_cursor: None }));
+        });
+
+        let filter = PostFilter { status: Some(PostStatus::Draft), ..PostFilter::default() };
+        assert!(get_all_posts(State(state), None, Query(filter)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_posts_author_lists_own_drafts() {
+        let state = setup(|mock| {
+            mock.expect_get_posts()
+                .withf(|filter| filter.status == Some(PostStatus::Draft) && filter.author == Some(AUTHOR_ID))
+                .returning(|_| Ok(Page { items: vec![gen_test_post()], next_cursor: None }));
+        });
+
+        let filter = PostFilter { status: Some(PostStatus::Draft), ..PostFilter::default() };
+        let response = get_all_posts(State(state), Some(AuthUser(gen_test_user(AUTHOR_ID, Role::Author))), Query(filter)).await.unwrap();
+        assert_eq!(response.0.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_post_by_author() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_update_post()
+                .withf(|_, editor, _| *editor == AUTHOR_ID)
+                .returning(|_, _, _| Ok(gen_test_post()));
+        });
+
+        let response = update_post(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Author)),
+            Path(gen_test_post().id),
+            Json(gen_update()),
+        ).await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_post_by_other_user_is_forbidden() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_update_post().never();
+        });
+
+        let response = update_post(
+            State(state),
+            AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Author)),
+            Path(gen_test_post().id),
+            Json(gen_update()),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_update_post_by_editor() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_update_post().returning(|_, _, _| Ok(gen_test_post()));
+        });
+
+        let response = update_post(
+            State(state),
+            AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Editor)),
+            Path(gen_test_post().id),
+            Json(gen_update()),
+        ).await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_post_by_author() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_delete_post().times(1).returning(|_| Ok(()));
+        });
+
+        let response = delete_post(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Author)),
+            Path(gen_test_post().id),
+        ).await.unwrap();
+        assert_eq!(response, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_delete_post_by_other_user_is_forbidden() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_delete_post().never();
+        });
+
+        let response = delete_post(
+            State(state),
+            AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Author)),
+            Path(gen_test_post().id),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_submit_post_by_author() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_transition_post()
+                .withf(|_, status, publish_at| *status == PostStatus::InReview && publish_at.is_none())
+                .returning(|_, _, _| Ok(Post { status: PostStatus::InReview, ..gen_test_post() }));
+        });
+
+        let response = submit_post(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Author)),
+            Path(gen_test_post().id),
+        ).await.unwrap();
+        assert_eq!(response.0.status, PostStatus::InReview);
+    }
+
+    #[tokio::test]
+    async fn test_submit_post_by_other_user_is_forbidden() {
+        let state = setup(|mock| {
+            mock.expect_get_post().returning(|_| Ok(gen_test_post()));
+            mock.expect_transition_post().never();
+        });
+
+        let response = submit_post(
+            State(state),
+            AuthUser(gen_test_user(uuid::Uuid::from_bytes([2; 16]), Role::Author)),
+            Path(gen_test_post().id),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_transition_is_conflict() {
+        let state = setup(|mock| {
+            mock.expect_transition_post().returning(|_, to, _| Err(DataError::InvalidTransition {
+                from: PostStatus::Draft,
+                to,
+            }));
+        });
+
+        let response = archive_post(State(state), Path(gen_test_post().id)).await.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_post_in_the_past_is_rejected() {
+        let state = setup(|mock| {
+            mock.expect_transition_post().never();
+        });
+
+        let publish_at = Utc::now() - Duration::minutes(1);
+        let response = schedule_post(State(state), Path(gen_test_post().id), Json(SchedulePost { publish_at })).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_post_in_the_future() {
+        let state = setup(|mock| {
+            mock.expect_transition_post()
+                .withf(|_, status, publish_at| *status == PostStatus::Scheduled && publish_at.is_some())
+                .returning(|_, status, publish_at| Ok(Post { status, publish_at, ..gen_test_post() }));
+        });
+
+        let publish_at = Utc::now() + Duration::hours(1);
+        let response = schedule_post(State(state), Path(gen_test_post().id), Json(SchedulePost { publish_at })).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
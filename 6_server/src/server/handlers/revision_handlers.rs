@@ -0,0 +1,165 @@
+use axum::extract::State;
+use axum::response::Result;
+
+use crate::data::data_errors::DataError;
+use crate::models::post::Post;
+use crate::models::revision::{DiffQuery, PostRevision, RevisionDiff};
+use crate::models::user::User;
+use crate::server::extract::{Json, Path, Query};
+use crate::server::middlewares::auth::AuthUser;
+use crate::services::{PostRepositoryProvider, RevisionRepositoryProvider, SessionRepositoryProvider};
+
+/// The revision history is part of the editorial audit trail, so only the post's author and editors get to see it.
+async fn ensure_can_edit<S: PostRepositoryProvider>(state: &S, user: &User, post_id: uuid::Uuid) -> std::result::Result<(), DataError> {
+    match state.post_repository().get_post(post_id).await?.can_be_edited_by(user) {
+        true => Ok(()),
+        false => Err(DataError::Forbidden),
+    }
+}
+
+pub async fn get_revisions<S: RevisionRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(post_id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<PostRevision>>> {
+    ensure_can_edit(&state, &user, post_id).await?;
+
+    Ok(Json(state.revision_repository().get_revisions(post_id).await?))
+}
+
+pub async fn get_revision<S: RevisionRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path((post_id, revision)): Path<(uuid::Uuid, i32)>,
+) -> Result<Json<PostRevision>> {
+    ensure_can_edit(&state, &user, post_id).await?;
+
+    Ok(Json(state.revision_repository().get_revision(post_id, revision).await?))
+}
+
+pub async fn get_revision_diff<S: RevisionRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path(post_id): Path<uuid::Uuid>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<RevisionDiff>> {
+    ensure_can_edit(&state, &user, post_id).await?;
+
+    let repository = state.revision_repository();
+    let from = repository.get_revision(post_id, query.from).await?;
+    let to = repository.get_revision(post_id, query.to).await?;
+
+    Ok(Json(RevisionDiff::between(&from, &to)))
+}
+
+pub async fn rollback_post<S: RevisionRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(user): AuthUser,
+    Path((post_id, revision)): Path<(uuid::Uuid, i32)>,
+) -> Result<Json<Post>> {
+    ensure_can_edit(&state, &user, post_id).await?;
+
+    Ok(Json(state.revision_repository().rollback_post(post_id, revision, user.id).await?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use chrono::DateTime;
+
+    use crate::data::repositories::post_repository::MockPostRepository;
+    use crate::data::repositories::revision_repository::MockRevisionRepository;
+    use crate::models::post::Title;
+    use crate::models::revision::DiffOp;
+    use crate::models::user::Role;
+    use crate::server::test_support::{gen_test_post, gen_test_user, MockProvider, AUTHOR_ID, POST_ID};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockRevisionRepository)) -> MockProvider {
+        let mut revisions = MockRevisionRepository::new();
+        f(&mut revisions);
+        let mut posts = MockPostRepository::new();
+        posts.expect_get_post().returning(|_| Ok(gen_test_post()));
+        MockProvider {
+            revisions: Arc::new(revisions),
+            posts: Arc::new(posts),
+            ..Default::default()
+        }
+    }
+
+    const EDITOR_ID: uuid::Uuid = uuid::Uuid::from_bytes([2; 16]);
+
+    fn gen_test_revision(revision: i32, body: &str) -> PostRevision {
+        PostRevision {
+            post_id: POST_ID,
+            revision,
+            title: Title::try_new("Test post").unwrap(),
+            body: body.to_string(),
+            editor: Some(AUTHOR_ID),
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_revisions_by_author() {
+        let state = setup(|mock| {
+            mock.expect_get_revisions()
+                .returning(|_| Ok(vec![gen_test_revision(2, "new"), gen_test_revision(1, "old")]));
+        });
+
+        let response = get_revisions(State(state), AuthUser(gen_test_user(AUTHOR_ID, Role::Author)), Path(POST_ID)).await.unwrap();
+        assert_eq!(response.0.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_revisions_by_other_user_is_forbidden() {
+        let state = setup(|mock| {
+            mock.expect_get_revisions().never();
+        });
+
+        let response = get_revisions(
+            State(state),
+            AuthUser(gen_test_user(EDITOR_ID, Role::Author)),
+            Path(POST_ID),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_revision_diff() {
+        let state = setup(|mock| {
+            mock.expect_get_revision()
+                .returning(|_, revision| Ok(gen_test_revision(revision, if revision == 1 { "old" } else { "new" })));
+        });
+
+        let response = get_revision_diff(
+            State(state),
+            AuthUser(gen_test_user(AUTHOR_ID, Role::Author)),
+            Path(POST_ID),
+            Query(DiffQuery { from: 1, to: 2 }),
+        ).await.unwrap();
+        let ops: Vec<DiffOp> = response.0.body.iter().map(|line| line.op).collect();
+        assert_eq!(ops, vec![DiffOp::Delete, DiffOp::Insert]);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_records_caller_as_editor() {
+        let state = setup(|mock| {
+            mock.expect_rollback_post()
+                .withf(|_, revision, editor| *revision == 1 && *editor == EDITOR_ID)
+                .returning(|_, _, _| Ok(gen_test_post()));
+        });
+
+        let response = rollback_post(
+            State(state),
+            AuthUser(gen_test_user(EDITOR_ID, Role::Editor)),
+            Path((POST_ID, 1)),
+        ).await;
+        assert!(response.is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use axum::extract::State;
+use axum::response::Result;
+
+use crate::models::search::{SearchQuery, SearchResult};
+use crate::server::extract::{Json, Query};
+use crate::services::SearchRepositoryProvider;
+
+pub async fn search_posts<S: SearchRepositoryProvider>(
+    State(state): State<S>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<SearchResult>>> {
+    if query.q.trim().is_empty() {
+        return Ok(Json(vec![]));
+    }
+
+    Ok(Json(state.search_repository().search_posts(query).await?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::data::repositories::search_repository::MockSearchRepository;
+    use crate::models::post::{Post, PostStatus, Title};
+    use crate::server::test_support::{gen_test_post, MockProvider};
+
+    use super::*;
+
+    fn setup(f: fn(&mut MockSearchRepository)) -> MockProvider {
+        let mut search = MockSearchRepository::new();
+        f(&mut search);
+        MockProvider {
+            search: Arc::new(search),
+            ..Default::default()
+        }
+    }
+
+    fn gen_test_result() -> SearchResult {
+        SearchResult {
+            post: Post {
+                slug: "rust-ownership".to_string(),
+                title: Title::try_new("Rust ownership").unwrap(),
+                body: "Borrowing rules explained".to_string(),
+                body_html: "<p>Borrowing rules explained</p>".to_string(),
+                excerpt: "Borrowing rules explained".to_string(),
+                status: PostStatus::Published,
+                ..gen_test_post()
+            },
+            rank: 0.6,
+            snippet: "<mark>Borrowing</mark> rules explained".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_posts() {
+        let state = setup(|mock| {
+            mock.expect_search_posts()
+                .withf(|query| query.q == "\"borrowing rules\" -unsafe")
+                .returning(|_| Ok(vec![gen_test_result()]));
+        });
+
+        let query = SearchQuery { q: "\"borrowing rules\" -unsafe".to_string(), limit: None };
+        let response = search_posts(State(state), Query(query)).await.unwrap();
+        assert_eq!(response.0.len(), 1);
+        assert_eq!(response.0[0].snippet, gen_test_result().snippet);
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_blank_query() {
+        let state = setup(|mock| {
+            mock.expect_search_posts().never();
+        });
+
+        let query = SearchQuery { q: "  ".to_string(), limit: None };
+        let response = search_posts(State(state), Query(query)).await.unwrap();
+        assert!(response.0.is_empty());
+    }
+}
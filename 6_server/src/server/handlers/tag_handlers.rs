@@ -0,0 +1,38 @@
+use axum::extract::State;
+use axum::response::Result;
+
+use crate::models::tag::TagCount;
+use crate::server::extract::Json;
+use crate::services::TagRepositoryProvider;
+
+pub async fn get_tags<S: TagRepositoryProvider>(
+    State(state): State<S>,
+) -> Result<Json<Vec<TagCount>>> {
+    Ok(Json(state.tag_repository().get_tags().await?))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::data::repositories::tag_repository::MockTagRepository;
+    use crate::models::tag::TagName;
+    use crate::server::test_support::MockProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_tags() {
+        let mut mock = MockTagRepository::new();
+        mock.expect_get_tags().returning(|| Ok(vec![TagCount {
+            name: TagName::try_new("Rust Lang").unwrap(),
+            post_count: 3,
+        }]));
+        let state = MockProvider { tags: Arc::new(mock), ..Default::default() };
+
+        let response = get_tags(State(state)).await.unwrap();
+        assert_eq!(response.0[0].name.to_string(), "rust-lang");
+        assert_eq!(response.0[0].post_count, 3);
+    }
+}
@@ -1,21 +1,65 @@
-use axum::extract::{Path, State};
-use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
 
-use crate::models::user::{CreateUser, User};
-use crate::services::UserRepositoryProvider;
+use crate::data::data_errors::DataError;
+use crate::models::user::{CreateUser, UpdateRole, UpdateUser, User, UserFilter};
+use crate::server::extract::{Json, Path, Query};
+use crate::server::middlewares::auth::AuthUser;
+use crate::services::{SessionRepositoryProvider, UserRepositoryProvider};
 
 pub async fn create_user<S: UserRepositoryProvider>(
     State(state): State<S>,
     Json(body): Json<CreateUser>,
 ) -> axum::response::Result<Json<User>> {
-    Ok(Json(state.user_repository().create_user(body)?))
+    Ok(Json(state.user_repository().create_user(body).await?))
 }
 
 pub async fn get_user<S: UserRepositoryProvider>(
     State(state): State<S>,
     Path(id): Path<uuid::Uuid>,
 ) -> axum::response::Result<Json<User>> {
-    Ok(Json(state.user_repository().get_user(id)?))
+    Ok(Json(state.user_repository().get_user(id).await?))
+}
+
+pub async fn get_users<S: UserRepositoryProvider>(
+    State(state): State<S>,
+    Query(filter): Query<UserFilter>,
+) -> axum::response::Result<Json<Vec<User>>> {
+    Ok(Json(state.user_repository().get_users(filter).await?))
+}
+
+pub async fn update_user<S: UserRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(caller): AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    Json(body): Json<UpdateUser>,
+) -> axum::response::Result<Json<User>> {
+    if !caller.can_manage(id) {
+        return Err(DataError::Forbidden.into());
+    }
+
+    Ok(Json(state.user_repository().update_user(id, body).await?))
+}
+
+pub async fn delete_user<S: UserRepositoryProvider + SessionRepositoryProvider>(
+    State(state): State<S>,
+    AuthUser(caller): AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> axum::response::Result<StatusCode> {
+    if !caller.can_manage(id) {
+        return Err(DataError::Forbidden.into());
+    }
+
+    state.user_repository().delete_user(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn update_user_role<S: UserRepositoryProvider>(
+    State(state): State<S>,
+    Path(id): Path<uuid::Uuid>,
+    Json(body): Json<UpdateRole>,
+) -> axum::response::Result<Json<User>> {
+    Ok(Json(state.user_repository().update_user_role(id, body.role).await?))
 }
 
 
@@ -25,65 +69,40 @@ mod test {
     use std::sync::Arc;
 
     use email_address::EmailAddress;
-    use mockall::mock;
 
-    use crate::data::data_errors::DataError;
-    use crate::data::repositories::user_repository::UserRepository;
-    use crate::models::user::Username;
-
-    use super::*;
-
-    mock! {
-        UserRepo {}
-        impl Clone for UserRepo {
-            fn clone(&self) -> Self;
-        }
-        impl UserRepository for UserRepo {
-            fn create_user(&self, create_user: CreateUser) -> Result<User, DataError>;
-            fn get_user(&self, id: uuid::Uuid) -> Result<User, DataError>;
-        }
-    }
+    use axum::response::IntoResponse;
 
-    #[derive(Clone)]
-    struct Provider {
-        repo: Arc<MockUserRepo>,
-    }
+    use crate::data::repositories::user_repository::MockUserRepository;
+    use crate::models::auth::Password;
+    use crate::models::user::{Role, Username};
+    use crate::server::test_support::{gen_test_user, MockProvider};
 
-    impl UserRepositoryProvider for Provider {
-        fn user_repository(&self) -> Arc<dyn UserRepository> {
-            self.repo.clone()
-        }
-    }
+    use super::*;
 
-    fn setup(f: fn(&mut MockUserRepo)) -> Provider {
-        let mut mock = MockUserRepo::new();
-        f(&mut mock);
-        Provider {
-            repo: Arc::new(mock),
-        }
-    }
+    const USER_ID: uuid::Uuid = uuid::Uuid::from_bytes([0; 16]);
 
-    fn gen_test_user() -> User {
-        User {
-            id: uuid::Uuid::from_bytes([0; 16]),
-            username: Username::new("test").unwrap(),
-            email: EmailAddress::from_str("test@test.com").unwrap(),
+    fn setup(f: fn(&mut MockUserRepository)) -> MockProvider {
+        let mut users = MockUserRepository::new();
+        f(&mut users);
+        MockProvider {
+            users: Arc::new(users),
+            ..Default::default()
         }
     }
 
-
     #[tokio::test]
     async fn test_create_user() {
-        let state = setup(|mock: &mut MockUserRepo|
+        let state = setup(|mock: &mut MockUserRepository|
         {
-            mock.UserRepository_expectations.create_user.expect().returning(|_| Ok(gen_test_user()));
+            mock.expect_create_user().returning(|_| Ok(gen_test_user(USER_ID, Role::Reader)));
         }
         );
 
 
         let user = CreateUser {
-            name: Username::new("test").unwrap(),
+            name: Username::try_new("test").unwrap(),
             email: EmailAddress::from_str("test@test.com").unwrap(),
+            password: Password::try_new("secret password").unwrap(),
         };
 
         let response = create_user(State(state.clone()), Json(user.clone())).await.unwrap();
@@ -94,10 +113,94 @@ mod test {
     #[tokio::test]
     async fn test_get_user() {
         let state = setup(move |mock| {
-            mock.UserRepository_expectations.get_user.expect().returning(|_| Ok(gen_test_user()));
+            mock.expect_get_user().returning(|_| Ok(gen_test_user(USER_ID, Role::Reader)));
         });
 
         let response = get_user(State(state.clone()), Path(uuid::Uuid::from_bytes([0; 16]))).await.unwrap();
-        assert_eq!(response.0, gen_test_user());
+        assert_eq!(response.0, gen_test_user(USER_ID, Role::Reader));
+    }
+
+    #[tokio::test]
+    async fn test_update_user_role() {
+        let state = setup(move |mock| {
+            mock.expect_update_user_role()
+                .withf(|_, role| *role == Role::Editor)
+                .returning(|_, role| Ok(User { role, ..gen_test_user(USER_ID, Role::Reader) }));
+        });
+
+        let response = update_user_role(
+            State(state.clone()),
+            Path(uuid::Uuid::from_bytes([0; 16])),
+            Json(UpdateRole { role: Role::Editor }),
+        ).await.unwrap();
+        assert_eq!(response.0.role, Role::Editor);
+    }
+
+    #[tokio::test]
+    async fn test_get_users() {
+        let state = setup(move |mock| {
+            mock.expect_get_users()
+                .withf(|filter| filter.username.as_deref() == Some("te") && filter.email.is_none())
+                .returning(|_| Ok(vec![gen_test_user(USER_ID, Role::Reader)]));
+        });
+
+        let filter = UserFilter { username: Some("te".to_string()), email: None };
+        let response = get_users(State(state.clone()), Query(filter)).await.unwrap();
+        assert_eq!(response.0, vec![gen_test_user(USER_ID, Role::Reader)]);
+    }
+
+    #[tokio::test]
+    async fn test_update_user() {
+        let state = setup(move |mock| {
+            mock.expect_update_user()
+                .returning(|_, update| Ok(User { username: update.name.unwrap(), ..gen_test_user(USER_ID, Role::Reader) }));
+        });
+
+        let update = UpdateUser { name: Some(Username::try_new("renamed").unwrap()), email: None };
+        let response = update_user(
+            State(state.clone()),
+            AuthUser(gen_test_user(USER_ID, Role::Reader)),
+            Path(gen_test_user(USER_ID, Role::Reader).id),
+            Json(update),
+        ).await.unwrap();
+        assert_eq!(response.0.username, Username::try_new("renamed").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_other_user_is_forbidden() {
+        let state = setup(move |mock| {
+            mock.expect_update_user().never();
+        });
+
+        let update = UpdateUser { name: Some(Username::try_new("renamed").unwrap()), email: None };
+        let response = update_user(
+            State(state.clone()),
+            AuthUser(gen_test_user(USER_ID, Role::Reader)),
+            Path(uuid::Uuid::from_bytes([1; 16])),
+            Json(update),
+        ).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_as_admin() {
+        let state = setup(move |mock| {
+            mock.expect_delete_user().returning(|_| Ok(()));
+        });
+
+        let admin = User { id: uuid::Uuid::from_bytes([1; 16]), role: Role::Admin, ..gen_test_user(USER_ID, Role::Reader) };
+        let response = delete_user(State(state.clone()), AuthUser(admin), Path(gen_test_user(USER_ID, Role::Reader).id)).await.unwrap();
+        assert_eq!(response, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_user() {
+        let state = setup(move |mock| {
+            mock.expect_delete_user().returning(|_| Err(DataError::NotFound));
+        });
+
+        let response = delete_user(State(state.clone()), AuthUser(gen_test_user(USER_ID, Role::Reader)), Path(gen_test_user(USER_ID, Role::Reader).id))
+            .await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 }
\ No newline at end of file
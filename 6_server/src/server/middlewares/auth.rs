@@ -0,0 +1,143 @@
+use axum::async_trait;
+use axum::extract::{FromRequestParts, Request};
+use axum::http::header::AUTHORIZATION;
+use axum::http::HeaderMap;
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::data::data_errors::DataError;
+use crate::models::auth::SessionToken;
+use crate::models::user::{Role, User};
+use crate::services::SessionRepositoryProvider;
+
+pub struct AuthUser(pub User);
+
+pub fn bearer_token(headers: &HeaderMap) -> Option<SessionToken> {
+    headers.get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| SessionToken::from(token.trim().to_string()))
+}
+
+#[async_trait]
+impl<S: SessionRepositoryProvider> FromRequestParts<S> for AuthUser {
+    type Rejection = DataError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<User>() {
+            return Ok(AuthUser(user.clone()));
+        }
+
+        let token = bearer_token(&parts.headers).ok_or(DataError::Unauthorized)?;
+
+        match state.session_repository().get_session_user(&token.hash()).await {
+            Ok(user) => {
+                tracing::Span::current().record("user_id", tracing::field::display(user.id));
+                parts.extensions.insert(user.clone());
+                Ok(AuthUser(user))
+            }
+            Err(DataError::NotFound) => Err(DataError::Unauthorized),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub trait RequiredRole: Send + Sync + 'static {
+    const ROLE: Role;
+}
+
+pub mod roles {
+    use crate::models::user::Role;
+
+    use super::RequiredRole;
+
+    pub struct Reader;
+    pub struct Author;
+    pub struct Editor;
+    pub struct Admin;
+
+    impl RequiredRole for Reader {
+        const ROLE: Role = Role::Reader;
+    }
+
+    impl RequiredRole for Author {
+        const ROLE: Role = Role::Author;
+    }
+
+    impl RequiredRole for Editor {
+        const ROLE: Role = Role::Editor;
+    }
+
+    impl RequiredRole for Admin {
+        const ROLE: Role = Role::Admin;
+    }
+}
+
+/// Route layer rejecting callers whose role is below `R`, e.g.
+/// `.route_layer(middleware::from_fn_with_state(state.clone(), require_role::<roles::Admin>))`.
+pub async fn require_role<R: RequiredRole>(
+    AuthUser(user): AuthUser,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, DataError> {
+    if user.role < R::ROLE {
+        return Err(DataError::Forbidden);
+    }
+
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::{middleware, Router};
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use crate::data::repositories::session_repository::MockSessionRepository;
+    use crate::server::test_support::{gen_test_user, MockProvider};
+
+    use super::*;
+
+    fn app() -> Router {
+        let mut mock = MockSessionRepository::new();
+        mock.expect_get_session_user().returning(|hash| {
+            let role = if hash == SessionToken::from("reader".to_string()).hash() {
+                Role::Reader
+            } else if hash == SessionToken::from("editor".to_string()).hash() {
+                Role::Editor
+            } else {
+                return Err(DataError::NotFound);
+            };
+            Ok(gen_test_user(uuid::Uuid::from_bytes([0; 16]), role))
+        });
+        let state = MockProvider { sessions: Arc::new(mock), ..Default::default() };
+
+        Router::new()
+            .route("/", get(|AuthUser(user): AuthUser| async move { user.role.to_string() }))
+            .route_layer(middleware::from_fn_with_state(state.clone(), require_role::<roles::Author>))
+            .with_state(state)
+    }
+
+    async fn status_for(token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri("/");
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_require_role() {
+        assert_eq!(status_for(None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status_for(Some("unknown")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status_for(Some("reader")).await, StatusCode::FORBIDDEN);
+        assert_eq!(status_for(Some("editor")).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::metrics::METRICS;
+
+/// Counts requests and observes their latency. Requests that matched no route share one
+/// label so scanners probing random paths cannot blow up the number of series, the same
+/// goes for extension methods.
+pub async fn http_metrics_middleware(
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = method_label(request.method());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let status = response.status();
+    let route = response.extensions().get::<MatchedPath>().map_or("unmatched", |route| route.as_str());
+    let labels = [method, route, status.as_str()];
+    METRICS.http_requests.with_label_values(&labels).inc();
+    METRICS.http_request_duration.with_label_values(&labels).observe(start.elapsed().as_secs_f64());
+
+    response
+}
+
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::HEAD => "HEAD",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::PATCH => "PATCH",
+        Method::OPTIONS => "OPTIONS",
+        Method::CONNECT => "CONNECT",
+        Method::TRACE => "TRACE",
+        _ => "other",
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extension_methods_share_a_label() {
+        assert_eq!(method_label(&Method::DELETE), "DELETE");
+        assert_eq!(method_label(&Method::from_bytes(b"PURGE").unwrap()), "other");
+    }
+}
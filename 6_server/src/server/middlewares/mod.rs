@@ -1 +1,4 @@
-pub mod tracing;
\ No newline at end of file
+pub mod tracing;
+pub mod auth;
+pub mod request_id;
+pub mod metrics;
@@ -1,19 +1,68 @@
-use axum::extract::Request;
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request};
 use axum::middleware::Next;
 use axum::response::Response;
-use tracing::info;
+use tracing::field::Empty;
+use tracing::{info, info_span, Instrument, Span};
+
+use crate::server::middlewares::request_id::current_request_id;
 
+/// Opens one span per request, everything logged while handling it carries the request id.
+/// `route` and `user_id` are filled in further down by `record_route` and `AuthUser`.
 pub async fn tracing_middleware(
     request: Request,
     next: Next,
 ) -> Response {
-    let uri = request.uri().clone();
+    let span = info_span!(
+        "request",
+        request_id = current_request_id().unwrap_or_default(),
+        method = %request.method(),
+        path = request.uri().path(),
+        route = Empty,
+        otel.name = Empty,
+        otel.kind = "server",
+        otel.status_code = Empty,
+        user_id = Empty,
+        status = Empty,
+        latency_ms = Empty,
+    );
+    let start = Instant::now();
+
+    async move {
+        let response = next.run(request).await;
 
-    info!("Request: {:?}", uri.path());
+        let span = Span::current();
+        span.record("status", response.status().as_u16());
+        if response.status().is_server_error() {
+            span.record("otel.status_code", "error");
+        }
+        span.record("latency_ms", start.elapsed().as_secs_f64() * 1000.0);
+        info!("finished request");
 
-    let response = next.run(request).await;
+        response
+    }
+        .instrument(span)
+        .await
+}
 
-    info!("Response: {:?} {:?}", uri.path(), response.status());
+/// Route layer recording the matched route, e.g. `/api/post/:id`, which is only known once
+/// the router has picked a route. It also names the exported span, e.g. `GET /api/post/:id`.
+pub async fn record_route(
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request.extensions().get::<MatchedPath>().cloned();
+    if let Some(route) = &route {
+        let span = Span::current();
+        span.record("route", route.as_str());
+        span.record("otel.name", format!("{} {}", request.method(), route.as_str()));
+    }
 
+    let mut response = next.run(request).await;
+    // Lets layers outside the router, e.g. `http_metrics_middleware`, label by route.
+    if let Some(route) = route {
+        response.extensions_mut().insert(route);
+    }
     response
-}
\ No newline at end of file
+}
@@ -2,5 +2,10 @@ pub mod state;
 pub mod handlers;
 pub mod routers;
 pub mod error_handlers;
+pub mod extract;
+pub mod validation;
 pub mod middlewares;
 pub mod app;
+pub mod templates;
+#[cfg(test)]
+pub(crate) mod test_support;
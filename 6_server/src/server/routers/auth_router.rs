@@ -0,0 +1,12 @@
+use axum::Router;
+use axum::routing::post;
+
+use crate::server::handlers::auth_handlers::{login, logout};
+use crate::services::{SessionRepositoryProvider, UserRepositoryProvider};
+
+pub fn auth_router<T: UserRepositoryProvider + SessionRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/login", post(login::<T>))
+        .route("/logout", post(logout::<T>))
+        .with_state(state)
+}
@@ -0,0 +1,12 @@
+use axum::Router;
+use axum::routing::{get, put};
+
+use crate::server::handlers::comment_handlers::{create_comment, get_comments, update_comment_status};
+use crate::services::{CommentRepositoryProvider, PostRepositoryProvider, SessionRepositoryProvider};
+
+pub fn comment_router<T: CommentRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/", get(get_comments::<T>).post(create_comment::<T>))
+        .route("/:comment_id/status", put(update_comment_status::<T>))
+        .with_state(state)
+}
@@ -0,0 +1,12 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::server::handlers::feed_handlers::{atom_feed, rss_feed};
+use crate::services::{PostRepositoryProvider, SiteProvider};
+
+pub fn feed_router<T: PostRepositoryProvider + SiteProvider>(state: T) -> Router {
+    Router::new()
+        .route("/feed.rss", get(rss_feed::<T>))
+        .route("/feed.atom", get(atom_feed::<T>))
+        .with_state(state)
+}
@@ -0,0 +1,12 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::server::handlers::health_handlers::{healthz, readyz};
+use crate::services::HealthRepositoryProvider;
+
+pub fn health_router<T: HealthRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz::<T>))
+        .with_state(state)
+}
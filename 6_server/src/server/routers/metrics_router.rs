@@ -0,0 +1,11 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::server::handlers::metrics_handlers::metrics;
+use crate::services::HealthRepositoryProvider;
+
+pub fn metrics_router<T: HealthRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics::<T>))
+        .with_state(state)
+}
@@ -1,2 +1,10 @@
 pub mod user_router;
-pub mod post_router;
\ No newline at end of file
+pub mod post_router;
+pub mod auth_router;
+pub mod comment_router;
+pub mod tag_router;
+pub mod revision_router;
+pub mod feed_router;
+pub mod page_router;
+pub mod metrics_router;
+pub mod health_router;
@@ -0,0 +1,13 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::server::handlers::page_handlers::{author_page, index_page, post_page};
+use crate::services::{PostRepositoryProvider, SiteProvider, UserRepositoryProvider};
+
+pub fn page_router<T: PostRepositoryProvider + UserRepositoryProvider + SiteProvider>(state: T) -> Router {
+    Router::new()
+        .route("/", get(index_page::<T>))
+        .route("/posts/:slug", get(post_page::<T>))
+        .route("/authors/:id", get(author_page::<T>))
+        .with_state(state)
+}
@@ -1,13 +1,28 @@
-use axum::Router;
+use axum::{middleware, Router};
 use axum::routing::{get, post, put};
 
-use crate::server::handlers::post_handlers::{create_post, get_all_posts, get_post, update_post};
-use crate::services::PostRepositoryProvider;
+use crate::server::handlers::post_handlers::{archive_post, create_post, delete_post, get_all_posts, get_deleted_posts, get_post, get_post_by_slug, publish_post, restore_post, return_post_to_draft, schedule_post, submit_post, update_post};
+use crate::server::handlers::search_handlers::search_posts;
+use crate::server::middlewares::auth::{require_role, roles};
+use crate::services::{PostRepositoryProvider, SearchRepositoryProvider, SessionRepositoryProvider};
+
+pub fn post_router<T: PostRepositoryProvider + SessionRepositoryProvider + SearchRepositoryProvider>(state: T) -> Router {
+    let author = middleware::from_fn_with_state(state.clone(), require_role::<roles::Author>);
+    let editor = middleware::from_fn_with_state(state.clone(), require_role::<roles::Editor>);
 
-pub fn post_router<T: PostRepositoryProvider>(state: T) -> Router {
     Router::new()
         .route("/", get(get_all_posts::<T>))
+        .route("/search", get(search_posts::<T>))
         .route("/:id", get(get_post::<T>))
-        .route("/", post(create_post::<T>))
-        .route("/:id", put(update_post::<T>)).with_state(state)
-}
\ No newline at end of file
+        .route("/by-slug/:slug", get(get_post_by_slug::<T>))
+        .route("/", post(create_post::<T>).route_layer(author.clone()))
+        .route("/:id", put(update_post::<T>).delete(delete_post::<T>).route_layer(author.clone()))
+        .route("/:id/submit", post(submit_post::<T>).route_layer(author.clone()))
+        .route("/:id/draft", post(return_post_to_draft::<T>).route_layer(author))
+        .route("/:id/schedule", post(schedule_post::<T>).route_layer(editor.clone()))
+        .route("/:id/publish", post(publish_post::<T>).route_layer(editor.clone()))
+        .route("/:id/archive", post(archive_post::<T>).route_layer(editor.clone()))
+        .route("/deleted", get(get_deleted_posts::<T>).route_layer(editor.clone()))
+        .route("/:id/restore", post(restore_post::<T>).route_layer(editor))
+        .with_state(state)
+}
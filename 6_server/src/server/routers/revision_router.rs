@@ -0,0 +1,14 @@
+use axum::Router;
+use axum::routing::{get, post};
+
+use crate::server::handlers::revision_handlers::{get_revision, get_revision_diff, get_revisions, rollback_post};
+use crate::services::{PostRepositoryProvider, RevisionRepositoryProvider, SessionRepositoryProvider};
+
+pub fn revision_router<T: RevisionRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/", get(get_revisions::<T>))
+        .route("/diff", get(get_revision_diff::<T>))
+        .route("/:revision", get(get_revision::<T>))
+        .route("/:revision/rollback", post(rollback_post::<T>))
+        .with_state(state)
+}
@@ -0,0 +1,11 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::server::handlers::tag_handlers::get_tags;
+use crate::services::TagRepositoryProvider;
+
+pub fn tag_router<T: TagRepositoryProvider>(state: T) -> Router {
+    Router::new()
+        .route("/", get(get_tags::<T>))
+        .with_state(state)
+}
@@ -1,12 +1,22 @@
-use axum::Router;
-use axum::routing::{get, post};
+use axum::{middleware, Router};
+use axum::routing::{get, post, put};
 
-use crate::server::handlers::user_handlers::{create_user, get_user};
-use crate::services::UserRepositoryProvider;
+use crate::server::handlers::feed_handlers::author_atom_feed;
+use crate::server::handlers::user_handlers::{create_user, delete_user, get_user, get_users, update_user, update_user_role};
+use crate::server::middlewares::auth::{require_role, roles};
+use crate::services::{PostRepositoryProvider, SessionRepositoryProvider, SiteProvider, UserRepositoryProvider};
+
+pub fn user_router<T: UserRepositoryProvider + SessionRepositoryProvider + PostRepositoryProvider + SiteProvider>(state: T) -> Router {
+    let admin = middleware::from_fn_with_state(state.clone(), require_role::<roles::Admin>);
 
-pub fn user_router<T: UserRepositoryProvider>(state: T) -> Router {
     Router::new()
         .route("/", post(create_user::<T>))
-        .route("/:id", get(get_user::<T>))
+        .route("/", get(get_users::<T>).route_layer(admin.clone()))
+        .route("/:id", get(get_user::<T>)
+            .put(update_user::<T>)
+            .patch(update_user::<T>)
+            .delete(delete_user::<T>))
+        .route("/:id/role", put(update_user_role::<T>).route_layer(admin))
+        .route("/:id/feed.atom", get(author_atom_feed::<T>))
         .with_state(state)
-}
\ No newline at end of file
+}
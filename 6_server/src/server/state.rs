@@ -1,14 +1,34 @@
 use std::sync::Arc;
 
+use crate::config::SiteConfig;
 use crate::data::repo_trait::DataRepository;
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::data::repositories::health_repository::HealthRepository;
 use crate::data::repositories::post_repository::PostRepository;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::data::repositories::tag_repository::TagRepository;
 use crate::data::repositories::user_repository::UserRepository;
-use crate::services::{PostRepositoryProvider, ServiceProvider, UserRepositoryProvider};
+use crate::services::{CommentRepositoryProvider, HealthRepositoryProvider, PostRepositoryProvider, RevisionRepositoryProvider, SearchRepositoryProvider, ServiceProvider, SessionRepositoryProvider, SiteProvider, TagRepositoryProvider, UserRepositoryProvider};
 
 #[derive(Clone)]
 pub struct AppState {
     pub user_repository: Arc<dyn UserRepository>,
     pub posts_repository: Arc<dyn PostRepository>,
+    pub session_repository: Arc<dyn SessionRepository>,
+    pub search_repository: Arc<dyn SearchRepository>,
+    pub comment_repository: Arc<dyn CommentRepository>,
+    pub tag_repository: Arc<dyn TagRepository>,
+    pub revision_repository: Arc<dyn RevisionRepository>,
+    pub health_repository: Arc<dyn HealthRepository>,
+    pub site: Arc<SiteConfig>,
+}
+
+impl AppState {
+    pub fn with_site(self, site: SiteConfig) -> Self {
+        AppState { site: Arc::new(site), ..self }
+    }
 }
 
 
@@ -16,7 +36,14 @@ impl<T: DataRepository> From<T> for AppState {
     fn from(repo: T) -> Self {
         AppState {
             user_repository: Arc::new(repo.clone()),
-            posts_repository: Arc::new(repo),
+            posts_repository: Arc::new(repo.clone()),
+            session_repository: Arc::new(repo.clone()),
+            search_repository: Arc::new(repo.clone()),
+            comment_repository: Arc::new(repo.clone()),
+            tag_repository: Arc::new(repo.clone()),
+            revision_repository: Arc::new(repo.clone()),
+            health_repository: Arc::new(repo),
+            site: Arc::new(SiteConfig::default()),
         }
     }
 }
@@ -33,4 +60,46 @@ impl PostRepositoryProvider for AppState {
     fn post_repository(&self) -> Arc<dyn PostRepository> {
         self.posts_repository.clone()
     }
-}
\ No newline at end of file
+}
+
+impl SessionRepositoryProvider for AppState {
+    fn session_repository(&self) -> Arc<dyn SessionRepository> {
+        self.session_repository.clone()
+    }
+}
+
+impl SearchRepositoryProvider for AppState {
+    fn search_repository(&self) -> Arc<dyn SearchRepository> {
+        self.search_repository.clone()
+    }
+}
+
+impl CommentRepositoryProvider for AppState {
+    fn comment_repository(&self) -> Arc<dyn CommentRepository> {
+        self.comment_repository.clone()
+    }
+}
+
+impl TagRepositoryProvider for AppState {
+    fn tag_repository(&self) -> Arc<dyn TagRepository> {
+        self.tag_repository.clone()
+    }
+}
+
+impl RevisionRepositoryProvider for AppState {
+    fn revision_repository(&self) -> Arc<dyn RevisionRepository> {
+        self.revision_repository.clone()
+    }
+}
+
+impl HealthRepositoryProvider for AppState {
+    fn health_repository(&self) -> Arc<dyn HealthRepository> {
+        self.health_repository.clone()
+    }
+}
+
+impl SiteProvider for AppState {
+    fn site(&self) -> Arc<SiteConfig> {
+        self.site.clone()
+    }
+}
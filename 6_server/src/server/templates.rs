@@ -0,0 +1,37 @@
+use askama::Template;
+
+use crate::config::SiteConfig;
+use crate::models::post::Post;
+use crate::models::user::User;
+
+#[derive(Template)]
+#[template(path = "index.html")]
+pub struct IndexPage<'a> {
+    pub site: &'a SiteConfig,
+    pub posts: &'a [Post],
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "post.html")]
+pub struct PostPage<'a> {
+    pub site: &'a SiteConfig,
+    pub post: &'a Post,
+    pub author: &'a User,
+}
+
+#[derive(Template)]
+#[template(path = "author.html")]
+pub struct AuthorPage<'a> {
+    pub site: &'a SiteConfig,
+    pub author: &'a User,
+    pub posts: &'a [Post],
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "error.html")]
+pub struct ErrorPage {
+    pub status: u16,
+    pub reason: &'static str,
+}
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use email_address::EmailAddress;
+
+use crate::config::SiteConfig;
+use crate::data::repositories::comment_repository::{CommentRepository, MockCommentRepository};
+use crate::data::repositories::health_repository::{HealthRepository, MockHealthRepository};
+use crate::data::repositories::post_repository::{MockPostRepository, PostRepository};
+use crate::data::repositories::revision_repository::{MockRevisionRepository, RevisionRepository};
+use crate::data::repositories::search_repository::{MockSearchRepository, SearchRepository};
+use crate::data::repositories::session_repository::{MockSessionRepository, SessionRepository};
+use crate::data::repositories::tag_repository::{MockTagRepository, TagRepository};
+use crate::data::repositories::user_repository::{MockUserRepository, UserRepository};
+use crate::models::post::{Post, PostStatus, Title};
+use crate::models::user::{Role, User, Username};
+use crate::services::{CommentRepositoryProvider, HealthRepositoryProvider, PostRepositoryProvider, RevisionRepositoryProvider, SearchRepositoryProvider, SessionRepositoryProvider, SiteProvider, TagRepositoryProvider, UserRepositoryProvider};
+
+pub const POST_ID: uuid::Uuid = uuid::Uuid::from_bytes([0; 16]);
+pub const AUTHOR_ID: uuid::Uuid = uuid::Uuid::from_bytes([1; 16]);
+
+/// State for handler tests. Repositories a test leaves at their default have no
+/// expectations, so any call to them fails the test.
+#[derive(Clone, Default)]
+pub struct MockProvider {
+    pub users: Arc<MockUserRepository>,
+    pub posts: Arc<MockPostRepository>,
+    pub sessions: Arc<MockSessionRepository>,
+    pub search: Arc<MockSearchRepository>,
+    pub comments: Arc<MockCommentRepository>,
+    pub tags: Arc<MockTagRepository>,
+    pub revisions: Arc<MockRevisionRepository>,
+    pub health: Arc<MockHealthRepository>,
+}
+
+impl UserRepositoryProvider for MockProvider {
+    fn user_repository(&self) -> Arc<dyn UserRepository> {
+        self.users.clone()
+    }
+}
+
+impl PostRepositoryProvider for MockProvider {
+    fn post_repository(&self) -> Arc<dyn PostRepository> {
+        self.posts.clone()
+    }
+}
+
+impl SessionRepositoryProvider for MockProvider {
+    fn session_repository(&self) -> Arc<dyn SessionRepository> {
+        self.sessions.clone()
+    }
+}
+
+impl SearchRepositoryProvider for MockProvider {
+    fn search_repository(&self) -> Arc<dyn SearchRepository> {
+        self.search.clone()
+    }
+}
+
+impl CommentRepositoryProvider for MockProvider {
+    fn comment_repository(&self) -> Arc<dyn CommentRepository> {
+        self.comments.clone()
+    }
+}
+
+impl TagRepositoryProvider for MockProvider {
+    fn tag_repository(&self) -> Arc<dyn TagRepository> {
+        self.tags.clone()
+    }
+}
+
+impl RevisionRepositoryProvider for MockProvider {
+    fn revision_repository(&self) -> Arc<dyn RevisionRepository> {
+        self.revisions.clone()
+    }
+}
+
+impl HealthRepositoryProvider for MockProvider {
+    fn health_repository(&self) -> Arc<dyn HealthRepository> {
+        self.health.clone()
+    }
+}
+
+impl SiteProvider for MockProvider {
+    fn site(&self) -> Arc<SiteConfig> {
+        Arc::new(SiteConfig { url: "https://blog.test".to_string(), title: "Test blog".to_string() })
+    }
+}
+
+pub fn gen_test_user(id: uuid::Uuid, role: Role) -> User {
+    User {
+        id,
+        username: Username::try_new("test").unwrap(),
+        email: EmailAddress::from_str("test@test.com").unwrap(),
+        role,
+        created_at: DateTime::from_timestamp(0, 0).unwrap(),
+        updated_at: DateTime::from_timestamp(0, 0).unwrap(),
+    }
+}
+
+/// A draft by `AUTHOR_ID`, tests override what they care about with struct update syntax.
+pub fn gen_test_post() -> Post {
+    Post {
+        id: POST_ID,
+        title: Title::try_new("Test post").unwrap(),
+        slug: "test-post".to_string(),
+        body: "body".to_string(),
+        body_html: "<p>body</p>".to_string(),
+        excerpt: "body".to_string(),
+        reading_time_minutes: 1,
+        status: PostStatus::Draft,
+        author: AUTHOR_ID,
+        tags: vec![],
+        created_at: DateTime::from_timestamp(0, 0).unwrap(),
+        updated_at: DateTime::from_timestamp(0, 0).unwrap(),
+        publish_at: None,
+        published_at: None,
+        deleted_at: None,
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use email_address::EmailAddress;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::models::auth::{LoginRequest, Password};
+use crate::models::comment::{CommentBody, CreateComment, UpdateCommentStatus};
+use crate::models::post::{CreatePost, PostFilter, SchedulePost, Title, UpdatePost};
+use crate::models::revision::DiffQuery;
+use crate::models::search::SearchQuery;
+use crate::models::tag::TagName;
+use crate::models::user::{CreateUser, UpdateRole, UpdateUser, UserFilter, Username};
+use crate::server::error_handlers::FieldViolation;
+
+/// A request body checked field by field, so a single response lists every invalid field.
+/// `Raw` has the shape of the body with plain types where the body has validated ones,
+/// e.g. `String` instead of `Title`.
+pub trait Validate: Sized {
+    type Raw: DeserializeOwned;
+
+    fn validate(raw: Self::Raw) -> Result<Self, Vec<FieldViolation>>;
+}
+
+#[derive(Default)]
+struct Violations(Vec<FieldViolation>);
+
+impl Violations {
+    fn check<T, E: Display>(&mut self, field: &str, result: Result<T, E>) -> Option<T> {
+        result
+            .map_err(|err| self.0.push(FieldViolation { field: field.to_string(), message: err.to_string() }))
+            .ok()
+    }
+
+    fn check_all<T, E: Display>(&mut self, field: &str, values: Vec<String>, f: fn(String) -> Result<T, E>) -> Option<Vec<T>> {
+        // Checks every value before collecting, collecting into `Option` stops at the first `None`.
+        let checked: Vec<Option<T>> = values.into_iter()
+            .enumerate()
+            .map(|(index, value)| self.check(&format!("{}[{}]", field, index), f(value)))
+            .collect();
+        checked.into_iter().collect()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawCreatePost {
+    title: String,
+    body: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl Validate for CreatePost {
+    type Raw = RawCreatePost;
+
+    fn validate(raw: RawCreatePost) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        let title = violations.check("title", Title::try_new(raw.title));
+        let tags = violations.check_all("tags", raw.tags, TagName::try_new);
+
+        match (title, tags) {
+            (Some(title), Some(tags)) => Ok(CreatePost { title, body: raw.body, tags }),
+            _ => Err(violations.0),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawUpdatePost {
+    title: Option<String>,
+    body: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+impl Validate for UpdatePost {
+    type Raw = RawUpdatePost;
+
+    fn validate(raw: RawUpdatePost) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        let title = raw.title.map(|title| violations.check("title", Title::try_new(title)));
+        let tags = raw.tags.map(|tags| violations.check_all("tags", tags, TagName::try_new));
+
+        if !violations.0.is_empty() {
+            return Err(violations.0);
+        }
+        Ok(UpdatePost { title: title.flatten(), body: raw.body, tags: tags.flatten() })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawCreateComment {
+    body: String,
+    parent_id: Option<uuid::Uuid>,
+}
+
+impl Validate for CreateComment {
+    type Raw = RawCreateComment;
+
+    fn validate(raw: RawCreateComment) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+
+        match violations.check("body", CommentBody::try_new(raw.body)) {
+            Some(body) => Ok(CreateComment { body, parent_id: raw.parent_id }),
+            None => Err(violations.0),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawCreateUser {
+    name: String,
+    email: String,
+    password: String,
+}
+
+impl Validate for CreateUser {
+    type Raw = RawCreateUser;
+
+    fn validate(raw: RawCreateUser) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        let name = violations.check("name", Username::try_new(raw.name));
+        let email = violations.check("email", EmailAddress::from_str(&raw.email));
+        let password = violations.check("password", Password::try_new(raw.password));
+
+        match (name, email, password) {
+            (Some(name), Some(email), Some(password)) => Ok(CreateUser { name, email, password }),
+            _ => Err(violations.0),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawUpdateUser {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Validate for UpdateUser {
+    type Raw = RawUpdateUser;
+
+    fn validate(raw: RawUpdateUser) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        let name = raw.name.map(|name| violations.check("name", Username::try_new(name)));
+        let email = raw.email.map(|email| violations.check("email", EmailAddress::from_str(&email)));
+
+        if !violations.0.is_empty() {
+            return Err(violations.0);
+        }
+        Ok(UpdateUser { name: name.flatten(), email: email.flatten() })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawLoginRequest {
+    username: String,
+    password: String,
+}
+
+impl Validate for LoginRequest {
+    type Raw = RawLoginRequest;
+
+    fn validate(raw: RawLoginRequest) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        let username = violations.check("username", Username::try_new(raw.username));
+        let password = violations.check("password", Password::try_new(raw.password));
+
+        match (username, password) {
+            (Some(username), Some(password)) => Ok(LoginRequest { username, password }),
+            _ => Err(violations.0),
+        }
+    }
+}
+
+/// `tags` stays the comma separated string the repositories split, blank names (e.g. after a
+/// trailing comma) are skipped but any other name has to be valid, otherwise the filter would
+/// silently match every post.
+impl Validate for PostFilter {
+    type Raw = Self;
+
+    fn validate(raw: Self) -> Result<Self, Vec<FieldViolation>> {
+        let mut violations = Violations::default();
+        for (index, name) in raw.tags.as_deref().unwrap_or_default().split(',').enumerate() {
+            if !name.trim().is_empty() {
+                violations.check(&format!("tags[{}]", index), TagName::try_new(name));
+            }
+        }
+
+        match violations.0.is_empty() {
+            true => Ok(raw),
+            false => Err(violations.0),
+        }
+    }
+}
+
+/// Bodies and queries without validated fields, serde already rejects them field by field.
+macro_rules! validated_by_serde {
+    ($($body:ty),*) => {
+        $(impl Validate for $body {
+            type Raw = Self;
+
+            fn validate(raw: Self) -> Result<Self, Vec<FieldViolation>> {
+                Ok(raw)
+            }
+        })*
+    };
+}
+
+validated_by_serde!(SchedulePost, UpdateCommentStatus, UpdateRole, DiffQuery, SearchQuery, UserFilter);
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fields(errors: Vec<FieldViolation>) -> Vec<String> {
+        errors.into_iter().map(|violation| violation.field).collect()
+    }
+
+    #[test]
+    fn test_every_invalid_field_is_reported() {
+        let raw = RawCreateUser { name: "x".to_string(), email: "not an email".to_string(), password: "short".to_string() };
+        assert_eq!(fields(CreateUser::validate(raw).unwrap_err()), vec!["name", "email", "password"]);
+
+        let raw = RawCreatePost { title: "Hi".to_string(), body: String::new(), tags: vec!["rust".to_string(), "".to_string()] };
+        assert_eq!(fields(CreatePost::validate(raw).unwrap_err()), vec!["title", "tags[1]"]);
+
+        let raw = RawUpdatePost { title: None, body: None, tags: Some(vec!["".to_string()]) };
+        assert_eq!(fields(UpdatePost::validate(raw).unwrap_err()), vec!["tags[0]"]);
+    }
+
+    #[test]
+    fn test_post_filter_rejects_invalid_tags() {
+        let filter = |tags: &str| PostFilter { tags: Some(tags.to_string()), ..PostFilter::default() };
+
+        assert_eq!(fields(PostFilter::validate(filter("!!!")).unwrap_err()), vec!["tags[0]"]);
+        assert_eq!(fields(PostFilter::validate(filter("rust,!!!,go")).unwrap_err()), vec!["tags[1]"]);
+        assert!(PostFilter::validate(filter("rust, Go,")).is_ok());
+    }
+}
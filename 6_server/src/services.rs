@@ -1,9 +1,16 @@
 use std::sync::Arc;
 
+use crate::config::SiteConfig;
+use crate::data::repositories::comment_repository::CommentRepository;
+use crate::data::repositories::health_repository::HealthRepository;
 use crate::data::repositories::post_repository::PostRepository;
+use crate::data::repositories::revision_repository::RevisionRepository;
+use crate::data::repositories::search_repository::SearchRepository;
+use crate::data::repositories::session_repository::SessionRepository;
+use crate::data::repositories::tag_repository::TagRepository;
 use crate::data::repositories::user_repository::UserRepository;
 
-pub trait ServiceProvider: UserRepositoryProvider + PostRepositoryProvider {}
+pub trait ServiceProvider: UserRepositoryProvider + PostRepositoryProvider + SessionRepositoryProvider + SearchRepositoryProvider + CommentRepositoryProvider + TagRepositoryProvider + RevisionRepositoryProvider + HealthRepositoryProvider + SiteProvider {}
 
 pub trait UserRepositoryProvider: Clone + Send + Sync + 'static {
     fn user_repository(&self) -> Arc<dyn UserRepository>;
@@ -12,3 +19,31 @@ pub trait UserRepositoryProvider: Clone + Send + Sync + 'static {
 pub trait PostRepositoryProvider: Clone + Send + Sync + 'static {
     fn post_repository(&self) -> Arc<dyn PostRepository>;
 }
+
+pub trait SessionRepositoryProvider: Clone + Send + Sync + 'static {
+    fn session_repository(&self) -> Arc<dyn SessionRepository>;
+}
+
+pub trait SearchRepositoryProvider: Clone + Send + Sync + 'static {
+    fn search_repository(&self) -> Arc<dyn SearchRepository>;
+}
+
+pub trait CommentRepositoryProvider: Clone + Send + Sync + 'static {
+    fn comment_repository(&self) -> Arc<dyn CommentRepository>;
+}
+
+pub trait TagRepositoryProvider: Clone + Send + Sync + 'static {
+    fn tag_repository(&self) -> Arc<dyn TagRepository>;
+}
+
+pub trait RevisionRepositoryProvider: Clone + Send + Sync + 'static {
+    fn revision_repository(&self) -> Arc<dyn RevisionRepository>;
+}
+
+pub trait HealthRepositoryProvider: Clone + Send + Sync + 'static {
+    fn health_repository(&self) -> Arc<dyn HealthRepository>;
+}
+
+pub trait SiteProvider: Clone + Send + Sync + 'static {
+    fn site(&self) -> Arc<SiteConfig>;
+}
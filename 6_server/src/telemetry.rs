@@ -0,0 +1,87 @@
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::config::TelemetryConfig;
+
+/// Builds a provider exporting spans over OTLP/HTTP, or `None` when no endpoint is configured
+/// and traces should only show up in the local logs.
+pub fn otlp_tracer_provider(conf: &TelemetryConfig) -> Result<Option<SdkTracerProvider>, anyhow::Error> {
+    let Some(endpoint) = conf.traces_endpoint() else {
+        return Ok(None);
+    };
+    if conf.protocol != "http/protobuf" {
+        return Err(anyhow::anyhow!("unsupported OTEL_EXPORTER_OTLP_PROTOCOL: {}, only http/protobuf is available", conf.protocol));
+    }
+
+    let exporter = SpanExporter::builder().with_http().with_endpoint(endpoint).build()?;
+
+    Ok(Some(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(conf.service_name.clone()).build())
+        .build()))
+}
+
+/// Forwards `tracing` spans, e.g. the per request span and the database query spans, to `provider`.
+pub fn layer<S>(provider: &SdkTracerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer("blog_server"))
+}
+
+
+#[cfg(test)]
+mod test {
+    use axum::body::Body;
+    use axum::http::Request;
+    use diesel::r2d2::ConnectionManager;
+    use diesel::SqliteConnection;
+    use opentelemetry::trace::SpanKind;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SpanData};
+    use r2d2::Pool;
+    use tower::ServiceExt;
+    use tracing::Instrument;
+    use tracing_subscriber::layer::SubscriberExt;
+    use uuid::Uuid;
+
+    use crate::data::blocking::with_connection;
+    use crate::data::memory::in_memory::InMemory;
+    use crate::server::app::define_app;
+    use crate::server::state::AppState;
+
+    use super::*;
+
+    fn span<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+        spans.iter().find(|span| span.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_spans_are_exported() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer(&provider)));
+
+        let app = define_app(AppState::from(InMemory::new()));
+        let request = Request::builder().uri(format!("/api/post/{}", Uuid::nil())).body(Body::empty()).unwrap();
+        app.oneshot(request).await.unwrap();
+
+        let pool = Pool::builder().max_size(1).build(ConnectionManager::<SqliteConnection>::new(":memory:")).unwrap();
+        with_connection(&pool, |_| Ok(())).instrument(tracing::info_span!("get_post")).await.unwrap();
+
+        provider.force_flush().unwrap();
+        let spans = exporter.get_finished_spans().unwrap();
+
+        let request = span(&spans, "GET /api/post/:id");
+        assert_eq!(request.span_kind, SpanKind::Server);
+        assert!(request.attributes.iter().any(|kv| kv.key.as_str() == "route" && kv.value.as_str() == "/api/post/:id"));
+
+        let query = span(&spans, "get_post");
+        let checkout = span(&spans, "db.pool.checkout");
+        assert_eq!(checkout.parent_span_id, query.span_context.span_id());
+    }
+}